@@ -1,7 +1,6 @@
 use std::{io::Cursor, process::Command};
 use clap::Parser;
 use color_print::cprintln;
-use tokio;
 
 const YEAR: u64 = 2025;
 
@@ -1,51 +1,62 @@
+use xmas::{
+    num::wrap_val,
+    parse::{self, ParseError, ParseErrorKind, ParseResult},
+};
 
-pub fn get_password_from_input(input: &str, start: isize, size: isize) -> isize {
-    let mut current = start;
+pub fn get_password_from_input(input: &str, start: isize, size: isize) -> ParseResult<isize> {
+    let mut current = wrap_val(start, size);
 
     let mut count = 0;
-    for line in input.lines() {
-        let indicator = &line[..1];
-        let is_right = indicator == "R";
-        let move_amount = line[1..].parse::<isize>().unwrap() % size;
+    for rotation in parse_rotations(input)? {
+        current = wrap_val(current + rotation, size);
 
-        current = if is_right { current + move_amount } else { current - move_amount };
-
-        // println!("{}, {}, {}", indicator, is_right, move_amount);
-        // println!("{} = {}", line, current);
-
-        if current % size == 0 {
+        if current == 0 {
             count += 1;
         }
     }
 
-    count
+    Ok(count)
 }
 
-pub fn get_new_password_from_input(input: &str, start: isize, size: isize) -> isize {
-    let mut current = start;
+/// Also counts every time the dial passes through 0 while rotating.
+pub fn get_new_password_from_input(input: &str, start: isize, size: isize) -> ParseResult<isize> {
+    let mut current = wrap_val(start, size);
 
     let mut count = 0;
-    for line in input.lines() {
-        let sign = match &line[..1] {
-            "L" => -1,
-            "R" => 1,
-            _ => panic!("Invalid direction"),
+    for rotation in parse_rotations(input)? {
+        // Multiples of `size` between the old position (excluded) and the new one (included)
+        count += if rotation >= 0 {
+            (current + rotation).div_euclid(size)
+        } else {
+            (current - 1).div_euclid(size) - (current + rotation - 1).div_euclid(size)
         };
-        let move_amount = line[1..].parse::<isize>().unwrap();
 
-        let rounds = move_amount / 100;
-        let move_amount = move_amount % 100;
-
-        let new_pos = (current + (move_amount * sign) + size) % size;
+        current = wrap_val(current + rotation, size);
+    }
 
-        let previous = current;
-        current = new_pos;
-        count += rounds;
+    Ok(count)
+}
 
-        if previous != 0 && ((sign > 0 && new_pos < previous) || (sign < 0 && new_pos > previous) || new_pos == 0) {
-            count += 1;
-        }
+/// Turns lines like `L68` and `R48` into signed amounts, left being negative.
+fn parse_rotations(input: &str) -> ParseResult<Vec<isize>> {
+    let numbers = parse::ints_spanned::<isize>(input)?;
+    let end = numbers.last().map_or(0, |n| n.span.end);
+    let rest = &input[end..];
+    let trailing = rest.trim();
+    if !trailing.is_empty() {
+        let offset = end + rest.len() - rest.trim_start().len();
+        return Err(ParseError::at(input, offset, ParseErrorKind::TrailingInput(trailing.to_string())));
     }
 
-    count
+    numbers
+        .into_iter()
+        .map(|number| match number.prefix.trim() {
+            "L" => Ok(-number.value),
+            "R" => Ok(number.value),
+            other => Err(ParseError::at(
+                input,
+                number.span.start - other.len(),
+                ParseErrorKind::Expected(format!("\"L\" or \"R\", found \"{other}\"")))),
+        })
+        .collect()
 }
@@ -57,7 +57,7 @@ fn is_valid_half(id: u64) -> bool {
     let s = id.to_string();
     let len: usize = s.len();
 
-    if len % 2 != 0 {
+    if !len.is_multiple_of(2) {
         return true;
     }
 
@@ -74,7 +74,7 @@ fn is_valid_any_amount(id: u64) -> bool {
             continue;
         }
 
-        if len % pattern_len != 0 {
+        if !len.is_multiple_of(pattern_len) {
             continue;
         }
 
@@ -1,7 +1,7 @@
 use std::{cmp::{max, min}, ops::RangeInclusive};
 
 use anyhow::{self, Context};
-use xmas::display_result;
+use xmas::{display_result, input::Sections};
 
 fn main() -> anyhow::Result<()> {
     part_1()?;
@@ -14,15 +14,10 @@ fn part_1() -> anyhow::Result<()> {
     println!("Part 1:");
     let input = std::fs::read_to_string("./input.txt").context("Error reading input file.")?;
 
-    let (first_s, second_s) = input.split_once("\n\n").unwrap();
-    let ranges = first_s
-        .lines()
-        .map(|l| parse_range(l))
-        .collect::<Vec<_>>();
+    let (ranges, ids) = Sections::new(&input).parse::<Database>()?;
 
     let mut result = 0;
-    for line in second_s.lines() {
-        let value = line.parse::<u64>().unwrap();
+    for value in ids {
         let contains = ranges.iter().any(|r| r.contains(&value));
         if contains {
             result += 1;
@@ -37,9 +32,7 @@ fn part_2() -> anyhow::Result<()> {
     println!("Part 2:");
     let input = std::fs::read_to_string("./input.txt").context("Error reading input file.")?;
 
-    let (first_s, _) = input.split_once("\n\n").unwrap();
-
-    let unmerged_ranges = first_s.lines().map(|l| parse_range(l)).collect::<Vec<_>>();
+    let (unmerged_ranges, _) = Sections::new(&input).parse::<Database>()?;
 
     let mut processed_ranges = unmerged_ranges.clone();
     // println!("{processed_ranges:?}");
@@ -90,9 +83,4 @@ fn part_2() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn parse_range(s: &str) -> RangeInclusive<u64> {
-    let (first_s, second_s) = s.split_once('-').unwrap();
-    let first = first_s.parse::<u64>().unwrap();
-    let second = second_s.parse::<u64>().unwrap();
-    first..=second
-}
+type Database = (Vec<RangeInclusive<u64>>, Vec<u64>);
@@ -1,7 +1,10 @@
-use std::{fmt::Debug, str::FromStr};
+use std::fmt::Debug;
 
 use anyhow::{self, Context};
-use xmas::{display_result, map2d::ByteMap, point2d::Point2D};
+use xmas::{
+    columns::{column_blocks, ColumnOrder},
+    display_result,
+};
 
 fn main() -> anyhow::Result<()> {
     part_1()?;
@@ -44,59 +47,20 @@ fn part_2() -> anyhow::Result<()> {
     let input = std::fs::read_to_string("./input.txt").context("Error reading input file.")?;
 
     let mut problems = Vec::new();
-
-    let map = ByteMap::from_str(&input)?;
-    let width = map.width() as isize;
-    let last_y = (map.height() - 1) as isize;
-
-    let mut x_start;
-    let mut x_end = 0;
-    loop {
-        x_start = x_end;
-        if x_start >= width {
-            break;
-        }
-
-        x_end = x_start + 1;
-
-        // println!("{}", Point2D(x_start, last_y));
-        let operator = match &map.get_tile(Point2D(x_start, last_y)).unwrap() {
-            b'*' => Operator::Mul,
-            b'+' => Operator::Add,
-            _ => unimplemented!("invalid operator")
-        };
-
-        while map.get_tile(Point2D(x_end, last_y)) == Some(&b' ') {
-            x_end += 1;
-        }
-
-        // This fixes the last column being ignored
-        if x_end >= width {
-            x_end = width + 1;
-        }
+    for block in column_blocks(&input)? {
+        let last_y = block.height() - 1;
 
         let mut problem = Problem::new();
-        problem.operator = operator;
-
-        for column in x_start..(x_end - 1) {
-            let mut num = 0;
-
-            for y in 0..last_y {
-                let b = *map.get_tile(Point2D(column, y)).unwrap();
-                let digit = match b {
-                    b' ' => continue,
-                    b'0'..=b'9' => (b - b'0') as i64,
-                    _ => unimplemented!()
-                };
-
-                num *= 10;
-                num += digit;
-            }
+        problem.operator = match block.row_text(last_y).trim() {
+            "*" => Operator::Mul,
+            "+" => Operator::Add,
+            other => anyhow::bail!("Invalid operator \"{other}\" @ column {}", block.x() + 1),
+        };
 
+        for num in block.rows(0..last_y).numbers_by_column::<i64>(ColumnOrder::RightToLeft)? {
             problem.add_num(num);
         }
 
-        // dbg!(&problem);
         problems.push(problem);
     }
 
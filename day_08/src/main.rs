@@ -50,7 +50,7 @@ fn part_2() -> anyhow::Result<()> {
     let mut circuits = Vec::<HashSet<usize>>::new();
 
     let mut i = 0;
-    while circuits.get(0).is_none_or(|c| c.len() < junctions.len()) {
+    while circuits.first().is_none_or(|c| c.len() < junctions.len()) {
         let conn = connections[i];
         merge_connections(&mut circuits, conn.0, conn.1);
 
@@ -68,7 +68,7 @@ fn part_2() -> anyhow::Result<()> {
 fn parse_junctions(input: &str) -> Result<Vec<Point3D>, ParsePoint3DError> {
     input
         .lines()
-        .map(|s| Point3D::from_str(s))
+        .map(Point3D::from_str)
         .collect::<Result<Vec<_>, _>>()
 }
 
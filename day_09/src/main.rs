@@ -1,7 +1,7 @@
-use std::{cmp, collections::HashMap};
+use std::collections::HashMap;
 use anyhow::{self, Context};
 use itertools::Itertools;
-use xmas::{direction::DIRECTIONS, display_result, map2d::ByteMap, point2d::Point2D};
+use xmas::{direction::DIRECTIONS, display_result, map2d::ByteMap, point2d::Point2D, raster, rect2d::Rect2D};
 
 fn main() -> anyhow::Result<()> {
     part_1()?;
@@ -42,9 +42,7 @@ fn find_largest_area(tiles: &[Point2D]) -> Option<usize> {
     let mut largest = None;
     for (i, from) in tiles.iter().enumerate() {
         for to in &tiles[(i + 1)..] {
-            let width = from.0.abs_diff(to.0) + 1;
-            let height = from.1.abs_diff(to.1) + 1;
-            let area = width * height;
+            let area = Rect2D::from_corners(*from, *to).area();
 
             if largest.is_none_or(|l| l < area) {
                 largest = Some(area);
@@ -72,32 +70,17 @@ fn find_largest_area_enclosed(red_tiles: &[Point2D]) -> Option<usize> {
 
     for (i, &point) in compressed_points.iter().enumerate() {
         let next_point = compressed_points[(i + 1) % compressed_points.len()];
-        let diff = next_point - point;
-
-        match diff {
-            Point2D(0, _) => {
-                let right = if next_point.1 > point.1 { Point2D(-1, 0) } else { Point2D(1, 0) };
-
-                let from = cmp::min(next_point.1, point.1);
-                let to = cmp::max(next_point.1, point.1);
-                for y in from..=to {
-                    let fill_point = Point2D(point.0, y);
-                    map.set_tile(fill_point, b'#');
-                    to_fill.push(fill_point + right);
-                }
-            },
-            Point2D(_, 0) => {
-                // let right = if next_point.1 > point.1 { Point2D(0, 1) } else { Point2D(0, -1) };
-
-                let from = cmp::min(next_point.0, point.0);
-                let to = cmp::max(next_point.0, point.0);
-                for x in from..=to {
-                    let fill_point = Point2D(x, point.1);
-                    map.set_tile(fill_point, b'#');
-                    // to_fill.push(fill_point + right);
-                }
-            },
+        let right = match next_point - point {
+            Point2D(0, dy) => Some(if dy > 0 { Point2D(-1, 0) } else { Point2D(1, 0) }),
+            Point2D(_, 0) => None,
             _ => unreachable!()
+        };
+
+        for fill_point in raster::line(point, next_point) {
+            map.set_tile(fill_point, b'#');
+            if let Some(right) = right {
+                to_fill.push(fill_point + right);
+            }
         }
     }
 
@@ -120,24 +103,15 @@ fn find_largest_area_enclosed(red_tiles: &[Point2D]) -> Option<usize> {
     let mut largest = None;
     for (i, &point) in compressed_points.iter().enumerate() {
         for (j, &other_point) in compressed_points[(i + 1)..].iter().enumerate() {
-            let from_x = cmp::min(point.0, other_point.0);
-            let to_x = cmp::max(point.0, other_point.0);
-            let from_y = cmp::min(point.1, other_point.1);
-            let to_y = cmp::max(point.1, other_point.1);
-
-            let all_filled = (from_x..=to_x)
-                .flat_map(|x| (from_y..=to_y).map(move |y| Point2D(x, y)))
+            let all_filled = Rect2D::from_corners(point, other_point)
+                .iter_points()
                 .all(|p| map.get_tile(p).is_some_and(|&t| t != b'.'));
             if !all_filled {
                 // println!("SKIP");
                 continue;
             }
 
-            let actual_point = red_tiles[i];
-            let actual_other_point = red_tiles[i + 1 + j];
-            let width = actual_point.0.abs_diff(actual_other_point.0) + 1;
-            let height = actual_point.1.abs_diff(actual_other_point.1) + 1;
-            let area = width * height;
+            let area = Rect2D::from_corners(red_tiles[i], red_tiles[i + 1 + j]).area();
 
             if largest.is_none_or(|l| l < area) {
                 largest = Some(area);
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
 use anyhow::{self, Context};
-use xmas::display_result;
+use xmas::{display_result, graph::Graph, memo::Memo};
 
 fn main() -> anyhow::Result<()> {
     part_1()?;
@@ -13,9 +12,11 @@ fn part_1() -> anyhow::Result<()> {
     println!("Part 1:");
     let input = std::fs::read_to_string("./input.txt").context("Error reading input file.")?;
 
-    let network = parse_network(&input);
+    let network = input.parse::<Network>()?;
 
-    let result = get_combination_count("you", &network);
+    let from = network.index_of("you").context("Missing \"you\" node")?;
+    let to = network.index_of("out").context("Missing \"out\" node")?;
+    let result = network.count_paths(from, to)?;
     display_result(&result);
     Ok(())
 }
@@ -24,89 +25,67 @@ fn part_2() -> anyhow::Result<()> {
     println!("Part 2:");
     let input = std::fs::read_to_string("./input.txt").context("Error reading input file.")?;
 
-    let network = parse_network(&input);
+    let network = input.parse::<Network>()?;
 
-    let result = get_combination_count_complete(&network);
+    let result = get_combination_count_complete(&network)?;
     display_result(&result);
 
     Ok(())
 }
 
-fn get_combination_count(from: &str, network: &Network) -> usize {
-    if from == "out" {
-        return 1;
-    }
-
-    network[from]
-        .iter()
-        .map(|n| get_combination_count(n, network))
-        .sum()
-}
-
-fn get_combination_count_complete(network: &Network) -> usize {
-    get_combination_count_cached(network, CacheKey::new("svr".to_string()), &mut HashMap::new())
-}
-
-fn get_combination_count_cached(network: &Network, next_key: CacheKey, cache: &mut HashMap<CacheKey, usize>) -> usize {
-    if let Some(&cached_val) = cache.get(&next_key) {
-        return cached_val;
-    }
+/// Paths from `svr` to `out` going through both `dac` and `fft`, in either order.
+fn get_combination_count_complete(network: &Network) -> anyhow::Result<u128> {
+    let [svr, dac, fft, out] = ["svr", "dac", "fft", "out"]
+        .map(|name| network.index_of(name).with_context(|| format!("Missing \"{name}\" node")));
+    let (svr, dac, fft, out) = (svr?, dac?, fft?, out?);
+    // Recursing around a cycle would never end
+    network.topological_sort_from(svr)?;
 
-    let node = next_key.node.as_str();
-    if node == "out" {
-        return if next_key.has_dac && next_key.has_fft { 1 } else { 0 };
-    }
-
-    let found_node = network.get(node);
-    if found_node.is_none() {
-        panic!("Node \"{node}\" not found");
-    }
-
-    let sum = found_node
-        .unwrap()
-        .iter()
-        .map(|n| get_combination_count_cached(
-            network,
-            CacheKey {
-                node: n.clone(),
-                has_dac: next_key.has_dac || node == "dac",
-                has_fft: next_key.has_fft || node == "fft",
-            },
-            cache))
-        .sum();
-
-    cache.insert(next_key.clone(), sum);
-    sum
+    get_combination_count_cached(network, [dac, fft, out], CacheKey::new(svr), &mut Memo::new())
+        .context("Too many paths")
 }
 
-fn parse_network(input: &str) -> Network {
-    let mut network = Network::new();
-    for line in input.lines() {
-        let (id, others) = line.split_once(": ").unwrap();
-
-        let mut other_ids = Node::new();
-        for other in others.split_whitespace() {
-            other_ids.push(other.to_string());
+/// `None` when there are too many paths to count.
+fn get_combination_count_cached(
+    network: &Network,
+    [dac, fft, out]: [usize; 3],
+    next_key: CacheKey,
+    cache: &mut Memo<CacheKey, Option<u128>>,
+) -> Option<u128> {
+    cache.get_or_compute(next_key, |cache, key| {
+        if key.node == out {
+            return Some(if key.has_dac && key.has_fft { 1 } else { 0 });
         }
 
-        network.insert(id.to_string(), other_ids);
-    }
-    network
+        network
+            .successors(key.node)
+            .iter()
+            .try_fold(0u128, |sum, &n| {
+                let paths = get_combination_count_cached(
+                    network,
+                    [dac, fft, out],
+                    CacheKey {
+                        node: n,
+                        has_dac: key.has_dac || key.node == dac,
+                        has_fft: key.has_fft || key.node == fft,
+                    },
+                    cache)?;
+                sum.checked_add(paths)
+            })
+    })
 }
 
-type ID = String;
-type Network = HashMap<ID, Node>;
-type Node = Vec<ID>;
+type Network = Graph<String>;
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 struct CacheKey {
-    pub node: String,
+    pub node: usize,
     pub has_dac: bool,
     pub has_fft: bool,
 }
 
 impl CacheKey {
-    pub fn new(node: String) -> Self {
+    pub fn new(node: usize) -> Self {
         Self { node, has_dac: false, has_fft: false }
     }
 }
@@ -12,7 +12,8 @@ fn part_1() -> anyhow::Result<()> {
     println!("Part 1:");
     let input = std::fs::read_to_string("./input.txt").context("Error reading input file.")?;
 
-    // display_result(&result);
+    let result = input.lines().count();
+    display_result(&result);
     Ok(())
 }
 
@@ -0,0 +1,191 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    direction::{Direction8, DIRECTIONS},
+    map2d::Map2D,
+    point2d::Point2D,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The 4 orthogonal neighbours.
+    VonNeumann,
+    /// The 8 orthogonal and diagonal neighbours.
+    Moore,
+}
+
+impl Neighbourhood {
+    fn offsets(&self) -> Vec<Point2D> {
+        match self {
+            Self::VonNeumann => DIRECTIONS.iter().map(|d| d.as_point()).collect(),
+            Self::Moore => Direction8::ALL.iter().map(|d| d.as_point()).collect(),
+        }
+    }
+}
+
+/// Tiles around the one being updated, as they were at the start of the step.
+pub struct Neighbours<'a, T> {
+    map: &'a Map2D<T>,
+    point: Point2D,
+    offsets: &'a [Point2D],
+}
+
+impl<'a, T> Neighbours<'a, T> {
+    pub fn point(&self) -> Point2D {
+        self.point
+    }
+
+    /// Neighbours inside the map. Tiles past the edges are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.offsets.iter().filter_map(|&offset| self.map.get_tile(self.point + offset))
+    }
+
+    pub fn count(&self, tile: &T) -> usize
+        where T: PartialEq
+    {
+        self.iter().filter(|&t| t == tile).count()
+    }
+}
+
+/// When a state came back, so the run can be skipped ahead. A fixed point has a period of 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub start: usize,
+    pub period: usize,
+}
+
+/// Updates every tile of a map at once from its previous state and neighbours.
+pub struct Automaton<T, Rule> {
+    map: Map2D<T>,
+    buffer: Map2D<T>,
+    offsets: Vec<Point2D>,
+    rule: Rule,
+    changes: Vec<usize>,
+}
+
+impl<T, Rule> Automaton<T, Rule>
+    where T: Clone + PartialEq,
+    Rule: FnMut(&T, &Neighbours<T>) -> T
+{
+    pub fn new(map: Map2D<T>, neighbourhood: Neighbourhood, rule: Rule) -> Self {
+        Self {
+            buffer: map.clone(),
+            map,
+            offsets: neighbourhood.offsets(),
+            rule,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn map(&self) -> &Map2D<T> {
+        &self.map
+    }
+
+    pub fn into_map(self) -> Map2D<T> {
+        self.map
+    }
+
+    /// Amount of steps done so far.
+    pub fn generation(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Amount of tiles that changed on each step, oldest first.
+    pub fn change_counts(&self) -> &[usize] {
+        &self.changes
+    }
+
+    /// Returns how many tiles changed.
+    pub fn step(&mut self) -> usize {
+        let mut changed = 0;
+        for point in self.map.iter_points() {
+            let tile = self.map.get_tile(point).unwrap();
+            let neighbours = Neighbours { map: &self.map, point, offsets: &self.offsets };
+            let new_tile = (self.rule)(tile, &neighbours);
+            if new_tile != *tile {
+                changed += 1;
+            }
+            self.buffer.set_tile(point, new_tile);
+        }
+
+        std::mem::swap(&mut self.map, &mut self.buffer);
+        self.changes.push(changed);
+        changed
+    }
+
+    /// Steps until nothing changes anymore and returns the total amount of changes. Never returns
+    /// if the map ends up cycling instead, see [`Automaton::run_until_repeat`] for those.
+    pub fn run_until_stable(&mut self) -> usize {
+        let mut total = 0;
+        loop {
+            match self.step() {
+                0 => return total,
+                changed => total += changed,
+            }
+        }
+    }
+}
+
+impl<T, Rule> Automaton<T, Rule>
+    where T: Clone + Eq + Hash,
+    Rule: FnMut(&T, &Neighbours<T>) -> T
+{
+    /// Steps until a previous state comes back.
+    pub fn run_until_repeat(&mut self) -> Repeat {
+        let mut seen = HashMap::new();
+        seen.insert(self.map.clone(), self.generation());
+        loop {
+            self.step();
+            if let Some(&start) = seen.get(&self.map) {
+                return Repeat { start, period: self.generation() - start };
+            }
+            seen.insert(self.map.clone(), self.generation());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map2d::ByteMap;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn life(tile: &u8, neighbours: &Neighbours<u8>) -> u8 {
+        match (tile, neighbours.count(&b'#')) {
+            (b'#', 2 | 3) | (b'.', 3) => b'#',
+            _ => b'.',
+        }
+    }
+
+    #[test]
+    fn steps_synchronously() {
+        let map = ByteMap::from_str(".....\n..#..\n..#..\n..#..\n.....\n").unwrap();
+        let mut automaton = Automaton::new(map, Neighbourhood::Moore, life);
+
+        assert_eq!(automaton.step(), 4);
+        assert_eq!(automaton.map().to_string(), ".....\n.....\n.###.\n.....\n.....\n");
+        assert_eq!(automaton.generation(), 1);
+    }
+
+    #[rstest]
+    #[case(".....\n..#..\n..#..\n..#..\n.....\n", Repeat { start: 0, period: 2 })]
+    #[case("....\n.##.\n.##.\n....\n", Repeat { start: 0, period: 1 })]
+    #[case("....\n.#..\n....\n....\n", Repeat { start: 1, period: 1 })]
+    fn finds_repeats(#[case] map: &str, #[case] expected: Repeat) {
+        let mut automaton = Automaton::new(ByteMap::from_str(map).unwrap(), Neighbourhood::Moore, life);
+        assert_eq!(automaton.run_until_repeat(), expected);
+    }
+
+    #[test]
+    fn runs_until_stable() {
+        let map = ByteMap::from_str("#####\n").unwrap();
+        let erode = |tile: &u8, neighbours: &Neighbours<u8>| {
+            if neighbours.count(&b'#') < 2 { b'.' } else { *tile }
+        };
+        let mut automaton = Automaton::new(map, Neighbourhood::VonNeumann, erode);
+
+        assert_eq!(automaton.run_until_stable(), 5);
+        assert_eq!(automaton.change_counts(), &[2, 2, 1, 0]);
+    }
+}
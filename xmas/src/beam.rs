@@ -0,0 +1,219 @@
+use std::{cell::RefCell, collections::HashSet};
+
+use crate::{
+    direction::Direction,
+    graph::{CountPathsError, Graph},
+    map2d::Map2D,
+    point2d::Point2D,
+};
+
+/// Something moving through a map, like light or a particle, about to enter `position`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Beam {
+    pub position: Point2D,
+    pub direction: Direction,
+}
+
+impl Beam {
+    pub fn new(position: Point2D, direction: Direction) -> Self {
+        Self { position, direction }
+    }
+
+    /// Next tile, same direction.
+    pub fn forward(&self) -> Self {
+        self.heading(self.direction)
+    }
+
+    /// Next tile when turning to `direction` on the current one.
+    pub fn heading(&self, direction: Direction) -> Self {
+        Self::new(self.position + direction.as_point(), direction)
+    }
+}
+
+/// What a tile does to a beam entering it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BeamAction {
+    /// Keeps going the same way.
+    Pass,
+    /// Stops here.
+    Absorb,
+    /// Continues in another direction, like off a mirror.
+    Reflect(Direction),
+    /// Continues as all of these beams at once. Splitting into no beams is the same as [`BeamAction::Absorb`].
+    Split(Vec<Beam>),
+}
+
+/// Every state beams went through. A beam reaching a state that was already seen merges into it,
+/// so loops and overlapping beams are only followed once.
+#[derive(Debug, Clone)]
+pub struct BeamPaths {
+    /// `None` is where beams end, by being absorbed or leaving the map.
+    graph: Graph<Option<Beam>>,
+    splitters: HashSet<Point2D>,
+    inside: Vec<bool>,
+}
+
+impl BeamPaths {
+    /// Distinct beams inside the map, by position and direction.
+    pub fn beams(&self) -> impl Iterator<Item = &Beam> + '_ {
+        self.graph.nodes().iter().zip(&self.inside).filter_map(|(b, &inside)| b.as_ref().filter(|_| inside))
+    }
+
+    /// Tiles at least one beam went through.
+    pub fn energized(&self) -> HashSet<Point2D> {
+        self.beams().map(|b| b.position).collect()
+    }
+
+    /// Tiles where beams split. A tile splitting beams coming from different directions only counts once.
+    pub fn splitters(&self) -> &HashSet<Point2D> {
+        &self.splitters
+    }
+
+    pub fn split_count(&self) -> usize {
+        self.splitters.len()
+    }
+
+    /// Amount of different ways a single particle could go from the start until it stops, as if
+    /// every split made it pick one of the branches. Fails if beams can loop forever, or if there are
+    /// too many paths to count.
+    pub fn path_count(&self) -> Result<u128, CountPathsError> {
+        match self.graph.index_of(&None) {
+            Some(end) => self.graph.count_paths(0, end),
+            None => Ok(self.graph.topological_sort().map(|_| 0)?),
+        }
+    }
+}
+
+impl<T> Map2D<T> {
+    /// Follows `start` and every beam it turns into, asking `rule` what each tile does to them.
+    pub fn propagate_beams(&self, start: Beam, rule: impl Fn(&T, &Beam) -> BeamAction) -> BeamPaths {
+        let splitters = RefCell::new(HashSet::new());
+        let successors = |state: &Option<Beam>| -> Vec<Option<Beam>> {
+            let Some(beam) = state else {
+                return Vec::new();
+            };
+            let Some(tile) = self.get_tile(beam.position) else {
+                return vec![None];
+            };
+
+            match rule(tile, beam) {
+                BeamAction::Pass => vec![Some(beam.forward())],
+                BeamAction::Absorb => vec![None],
+                BeamAction::Reflect(direction) => vec![Some(beam.heading(direction))],
+                BeamAction::Split(beams) if beams.is_empty() => vec![None],
+                BeamAction::Split(beams) => {
+                    splitters.borrow_mut().insert(beam.position);
+                    beams.into_iter().map(Some).collect()
+                },
+            }
+        };
+
+        let graph = Graph::explore(Some(start), successors, None);
+        let inside = graph.nodes().iter().map(|b| b.is_some_and(|b| self.is_inside(b.position))).collect();
+        BeamPaths { graph, splitters: splitters.into_inner(), inside }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map2d::ByteMap;
+    use std::str::FromStr;
+
+    const MIRRORS: &str = concat!(
+        ".|...\\....\n",
+        "|.-.\\.....\n",
+        ".....|-...\n",
+        "........|.\n",
+        "..........\n",
+        ".........\\\n",
+        "..../.\\\\..\n",
+        ".-.-/..|..\n",
+        ".|....-|.\\\n",
+        "..//.|....\n",
+    );
+
+    fn mirror_rule(tile: &u8, beam: &Beam) -> BeamAction {
+        use Direction::*;
+        let horizontal = matches!(beam.direction, Left | Right);
+        match (tile, beam.direction) {
+            (b'/', Right) | (b'\\', Left) => BeamAction::Reflect(Up),
+            (b'/', Left) | (b'\\', Right) => BeamAction::Reflect(Down),
+            (b'/', Up) | (b'\\', Down) => BeamAction::Reflect(Right),
+            (b'/', Down) | (b'\\', Up) => BeamAction::Reflect(Left),
+            (b'|', _) if horizontal => BeamAction::Split(vec![beam.heading(Up), beam.heading(Down)]),
+            (b'-', _) if !horizontal => BeamAction::Split(vec![beam.heading(Left), beam.heading(Right)]),
+            _ => BeamAction::Pass,
+        }
+    }
+
+    fn splitter_rule(tile: &u8, beam: &Beam) -> BeamAction {
+        match tile {
+            b'^' => BeamAction::Split(vec![
+                Beam::new(beam.position + Direction::Left.as_point(), beam.direction),
+                Beam::new(beam.position + Direction::Right.as_point(), beam.direction),
+            ]),
+            b'#' => BeamAction::Absorb,
+            _ => BeamAction::Pass,
+        }
+    }
+
+    #[test]
+    fn energizes_tiles_through_loops() {
+        let map = ByteMap::from_str(MIRRORS).unwrap();
+        let paths = map.propagate_beams(Beam::new(Point2D(0, 0), Direction::Right), mirror_rule);
+
+        assert_eq!(paths.energized().len(), 46);
+        assert!(paths.path_count().is_err());
+    }
+
+    #[test]
+    fn counts_splitters_once() {
+        let map = ByteMap::from_str(concat!(
+            "./-\\.\n",
+            ".....\n",
+            ".\\|/.\n",
+        )).unwrap();
+        let paths = map.propagate_beams(Beam::new(Point2D(2, 0), Direction::Down), mirror_rule);
+
+        // Both splitters are hit from two opposite directions
+        assert_eq!(paths.beams().filter(|b| b.position == Point2D(2, 2)).count(), 2);
+        assert_eq!(paths.split_count(), 2);
+        assert_eq!(paths.splitters(), &HashSet::from([Point2D(2, 0), Point2D(2, 2)]));
+    }
+
+    #[test]
+    fn counts_splits_and_paths() {
+        let map = ByteMap::from_str(concat!(
+            "..S..\n",
+            "..^..\n",
+            ".^.^.\n",
+            "..#..\n",
+            ".....\n",
+        )).unwrap();
+        let paths = map.propagate_beams(Beam::new(Point2D(2, 0), Direction::Down), splitter_rule);
+
+        assert_eq!(paths.split_count(), 3);
+        assert_eq!(paths.path_count(), Ok(4));
+        assert_eq!(paths.energized().len(), 14);
+    }
+
+    #[test]
+    fn empty_splits_absorb() {
+        let map = ByteMap::from_str("..\n").unwrap();
+        let paths = map.propagate_beams(Beam::new(Point2D(0, 0), Direction::Right), |_, _| BeamAction::Split(vec![]));
+
+        assert_eq!(paths.beams().count(), 1);
+        assert_eq!(paths.split_count(), 0);
+        assert_eq!(paths.path_count(), Ok(1));
+    }
+
+    #[test]
+    fn absorbed_beams_count_as_paths() {
+        let map = ByteMap::from_str(".#\n").unwrap();
+        let paths = map.propagate_beams(Beam::new(Point2D(0, 0), Direction::Right), splitter_rule);
+
+        assert_eq!(paths.beams().count(), 2);
+        assert_eq!(paths.path_count(), Ok(1));
+    }
+}
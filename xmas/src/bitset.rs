@@ -0,0 +1,296 @@
+use std::{fmt::Display, ops, str::FromStr};
+use thiserror::Error;
+
+const WORD_BITS: usize = u64::BITS as usize;
+const INLINE_WORDS: usize = 2;
+
+/// Fixed-size set of bits. Up to 128 bits are stored inline, bigger sets go to the heap.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    len: usize,
+    words: Words,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Words {
+    Inline([u64; INLINE_WORDS]),
+    Heap(Box<[u64]>),
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> Self {
+        let word_count = len.div_ceil(WORD_BITS);
+        let words = if word_count <= INLINE_WORDS {
+            Words::Inline([0; INLINE_WORDS])
+        } else {
+            Words::Heap(vec![0; word_count].into_boxed_slice())
+        };
+        Self { len, words }
+    }
+
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut set = Self::new(len);
+        for i in indices {
+            set.insert(i);
+        }
+        set
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.check_index(index);
+        self.words()[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        if value { self.insert(index) } else { self.remove(index) }
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        self.check_index(index);
+        self.words_mut()[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.check_index(index);
+        self.words_mut()[index / WORD_BITS] &= !(1 << (index % WORD_BITS));
+    }
+
+    pub fn toggle(&mut self, index: usize) {
+        self.check_index(index);
+        self.words_mut()[index / WORD_BITS] ^= 1 << (index % WORD_BITS);
+    }
+
+    pub fn clear(&mut self) {
+        self.words_mut().fill(0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn any(&self) -> bool {
+        self.words().iter().any(|&w| w != 0)
+    }
+
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    /// Indices of the set bits, in increasing order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &word)| {
+                let mut remaining = word;
+                std::iter::from_fn(move || {
+                    if remaining == 0 {
+                        return None;
+                    }
+                    let bit = remaining.trailing_zeros() as usize;
+                    remaining &= remaining - 1;
+                    Some(i * WORD_BITS + bit)
+                })
+            })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    fn words(&self) -> &[u64] {
+        let word_count = self.len.div_ceil(WORD_BITS);
+        match &self.words {
+            Words::Inline(words) => &words[..word_count],
+            Words::Heap(words) => words,
+        }
+    }
+
+    fn words_mut(&mut self) -> &mut [u64] {
+        let word_count = self.len.div_ceil(WORD_BITS);
+        match &mut self.words {
+            Words::Inline(words) => &mut words[..word_count],
+            Words::Heap(words) => words,
+        }
+    }
+
+    fn check_index(&self, index: usize) {
+        assert!(index < self.len, "bit {index} out of bounds for a BitSet of {}", self.len);
+    }
+
+    fn combine_with(&mut self, other: &BitSet, op: impl Fn(&mut u64, u64)) {
+        assert_eq!(self.len, other.len, "BitSets should have the same length");
+        for (a, &b) in self.words_mut().iter_mut().zip(other.words()) {
+            op(a, b);
+        }
+    }
+}
+
+impl ops::BitXorAssign<&BitSet> for BitSet {
+    fn bitxor_assign(&mut self, rhs: &BitSet) {
+        self.combine_with(rhs, |a, b| *a ^= b);
+    }
+}
+
+impl ops::BitAndAssign<&BitSet> for BitSet {
+    fn bitand_assign(&mut self, rhs: &BitSet) {
+        self.combine_with(rhs, |a, b| *a &= b);
+    }
+}
+
+impl ops::BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, rhs: &BitSet) {
+        self.combine_with(rhs, |a, b| *a |= b);
+    }
+}
+
+impl ops::BitXor<&BitSet> for BitSet {
+    type Output = BitSet;
+
+    fn bitxor(mut self, rhs: &BitSet) -> Self::Output {
+        self ^= rhs;
+        self
+    }
+}
+
+impl ops::BitAnd<&BitSet> for BitSet {
+    type Output = BitSet;
+
+    fn bitand(mut self, rhs: &BitSet) -> Self::Output {
+        self &= rhs;
+        self
+    }
+}
+
+impl ops::BitOr<&BitSet> for BitSet {
+    type Output = BitSet;
+
+    fn bitor(mut self, rhs: &BitSet) -> Self::Output {
+        self |= rhs;
+        self
+    }
+}
+
+impl FromIterator<bool> for BitSet {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits = iter.into_iter().collect::<Vec<_>>();
+        Self::from_indices(bits.len(), bits.iter().enumerate().filter(|(_, &b)| b).map(|(i, _)| i))
+    }
+}
+
+/// Uses the indicator light notation, `[.##.]`.
+impl Display for BitSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for bit in self.iter() {
+            write!(f, "{}", if bit { '#' } else { '.' })?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Parses the indicator light notation, `[.##.]`. The brackets are optional.
+impl FromStr for BitSet {
+    type Err = ParseBitSetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Byte offset of `inner` in `s`, so errors point into the original text
+        let (inner, offset) = match s.strip_prefix('[') {
+            Some(rest) => (rest.strip_suffix(']').ok_or(ParseBitSetError::UnclosedBracket)?, 1),
+            None => (s, 0),
+        };
+
+        inner
+            .char_indices()
+            .map(|(i, ch)| match ch {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(ParseBitSetError::InvalidChar(ch, offset + i)),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ParseBitSetError {
+    #[error("Missing closing ']'")]
+    UnclosedBracket,
+    /// The offset is in bytes, from the start of the parsed text.
+    #[error("Invalid char '{0}' @ {1}, expected '#' or '.'")]
+    InvalidChar(char, usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    #[rstest]
+    #[case("[.##.]", 4, vec![1, 2])]
+    #[case("#...#", 5, vec![0, 4])]
+    #[case("[]", 0, vec![])]
+    fn parses_indicator_notation(#[case] s: &str, #[case] len: usize, #[case] expected: Vec<usize>) {
+        let set = BitSet::from_str(s).unwrap();
+
+        assert_eq!(set.len(), len);
+        assert_eq!(set.iter_ones().collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    #[case("[.#x]", ParseBitSetError::InvalidChar('x', 3))]
+    #[case(".#x", ParseBitSetError::InvalidChar('x', 2))]
+    #[case("[é#x]", ParseBitSetError::InvalidChar('é', 1))]
+    #[case("[.#", ParseBitSetError::UnclosedBracket)]
+    fn parse_returns_errors(#[case] s: &str, #[case] expected: ParseBitSetError) {
+        assert_eq!(BitSet::from_str(s), Err(expected));
+    }
+
+    #[test]
+    fn displays_indicator_notation() {
+        assert_eq!(BitSet::from_indices(4, [1, 2]).to_string(), "[.##.]");
+    }
+
+    #[rstest]
+    #[case(10)]
+    #[case(128)]
+    #[case(300)]
+    fn combines_sets(#[case] len: usize) {
+        let a = BitSet::from_indices(len, [0, 3, len - 1]);
+        let b = BitSet::from_indices(len, [3, 5]);
+
+        assert_eq!((a.clone() ^ &b).iter_ones().collect::<Vec<_>>(), vec![0, 5, len - 1]);
+        assert_eq!((a.clone() & &b).iter_ones().collect::<Vec<_>>(), vec![3]);
+        assert_eq!((a.clone() | &b).count_ones(), 4);
+    }
+
+    #[test]
+    fn toggles_and_counts_past_inline_size() {
+        let mut set = BitSet::new(200);
+        for i in (0..200).step_by(3) {
+            set.toggle(i);
+        }
+        set.toggle(0);
+
+        assert_eq!(set.count_ones(), 66);
+        assert!(!set.get(0));
+        assert!(set.get(198));
+    }
+
+    #[test]
+    fn hashes_by_value() {
+        let mut seen = HashSet::new();
+        seen.insert(BitSet::from_indices(70, [1, 65]));
+
+        assert!(seen.contains(&BitSet::from_indices(70, [65, 1])));
+        assert!(!seen.contains(&BitSet::from_indices(71, [1, 65])));
+    }
+}
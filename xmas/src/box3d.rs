@@ -0,0 +1,216 @@
+use std::fmt::Display;
+
+use crate::point3d::Point3D;
+
+/// Axis-aligned box. Both corners are inclusive, so it always holds at least one point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Box3D {
+    min: Point3D,
+    max: Point3D,
+}
+
+impl Box3D {
+    /// Box between any two opposite corners.
+    pub fn from_corners(a: Point3D, b: Point3D) -> Self {
+        Self { min: a.min(b), max: a.max(b) }
+    }
+
+    /// Box of `size` starting at `origin`, if it isn't empty.
+    pub fn from_size(origin: Point3D, size: Point3D) -> Option<Self> {
+        (size.0 > 0 && size.1 > 0 && size.2 > 0).then(|| Self { min: origin, max: origin + size - Point3D(1, 1, 1) })
+    }
+
+    pub fn min(&self) -> Point3D {
+        self.min
+    }
+
+    pub fn max(&self) -> Point3D {
+        self.max
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.0.abs_diff(self.min.0) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.1.abs_diff(self.min.1) + 1
+    }
+
+    pub fn depth(&self) -> usize {
+        self.max.2.abs_diff(self.min.2) + 1
+    }
+
+    pub fn size(&self) -> Point3D {
+        Point3D(self.width() as isize, self.height() as isize, self.depth() as isize)
+    }
+
+    /// `None` if it doesn't fit in a `usize`.
+    pub fn volume(&self) -> Option<usize> {
+        self.width().checked_mul(self.height())?.checked_mul(self.depth())
+    }
+
+    pub fn contains(&self, point: Point3D) -> bool {
+        self.min.max(point) == point && self.max.min(point) == point
+    }
+
+    pub fn contains_box(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (min.0 <= max.0 && min.1 <= max.1 && min.2 <= max.2).then_some(Self { min, max })
+    }
+
+    /// Smallest box holding both.
+    pub fn union(&self, other: &Self) -> Self {
+        Self { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// Layer by layer along z, each of them row by row.
+    pub fn iter_points(&self) -> impl Iterator<Item = Point3D> + '_ {
+        let Self { min, max } = *self;
+        (min.2..=max.2).flat_map(move |z| {
+            (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| Point3D(x, y, z)))
+        })
+    }
+
+    /// Points on the outer faces, each of them once.
+    pub fn iter_surface(&self) -> impl Iterator<Item = Point3D> + '_ {
+        self.iter_points().filter(|p| {
+            p.0 == self.min.0 || p.0 == self.max.0
+                || p.1 == self.min.1 || p.1 == self.max.1
+                || p.2 == self.min.2 || p.2 == self.max.2
+        })
+    }
+
+    /// Front face clockwise from the top left corner like [`crate::rect2d::Rect2D::corners`], then
+    /// the back face in the same order.
+    pub fn corners(&self) -> [Point3D; 8] {
+        let Self { min, max } = *self;
+        [
+            min, Point3D(max.0, min.1, min.2), Point3D(max.0, max.1, min.2), Point3D(min.0, max.1, min.2),
+            Point3D(min.0, min.1, max.2), Point3D(max.0, min.1, max.2), max, Point3D(min.0, max.1, max.2),
+        ]
+    }
+
+    /// What's left after cutting `other` out, as up to 6 boxes that don't overlap.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        // Slices off whole layers along z, then y within the cut's z range, then x
+        let mut pieces = Vec::new();
+        let mut rest = *self;
+        if cut.min.2 > rest.min.2 {
+            pieces.push(Self { min: rest.min, max: Point3D(rest.max.0, rest.max.1, cut.min.2 - 1) });
+        }
+        if cut.max.2 < rest.max.2 {
+            pieces.push(Self { min: Point3D(rest.min.0, rest.min.1, cut.max.2 + 1), max: rest.max });
+        }
+        rest = Self { min: Point3D(rest.min.0, rest.min.1, cut.min.2), max: Point3D(rest.max.0, rest.max.1, cut.max.2) };
+
+        if cut.min.1 > rest.min.1 {
+            pieces.push(Self { min: rest.min, max: Point3D(rest.max.0, cut.min.1 - 1, rest.max.2) });
+        }
+        if cut.max.1 < rest.max.1 {
+            pieces.push(Self { min: Point3D(rest.min.0, cut.max.1 + 1, rest.min.2), max: rest.max });
+        }
+        rest = Self { min: Point3D(rest.min.0, cut.min.1, rest.min.2), max: Point3D(rest.max.0, cut.max.1, rest.max.2) };
+
+        if cut.min.0 > rest.min.0 {
+            pieces.push(Self { min: rest.min, max: Point3D(cut.min.0 - 1, rest.max.1, rest.max.2) });
+        }
+        if cut.max.0 < rest.max.0 {
+            pieces.push(Self { min: Point3D(cut.max.0 + 1, rest.min.1, rest.min.2), max: rest.max });
+        }
+        pieces
+    }
+}
+
+impl Display for Box3D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    #[rstest]
+    #[case(Point3D(0, 0, 0), Point3D(2, 3, 4), 60)]
+    #[case(Point3D(2, 3, 4), Point3D(0, 0, 0), 60)]
+    #[case(Point3D(-1, 5, 5), Point3D(-1, 5, 5), 1)]
+    fn volume_is_inclusive(#[case] a: Point3D, #[case] b: Point3D, #[case] expected: usize) {
+        let cube = Box3D::from_corners(a, b);
+
+        assert_eq!(cube.volume(), Some(expected));
+        assert_eq!(cube.iter_points().count(), expected);
+        assert!(cube.contains(a) && cube.contains(b));
+    }
+
+    #[test]
+    fn volume_fails_when_too_big() {
+        let cube = Box3D::from_corners(Point3D(0, 0, 0), Point3D(1 << 30, 1 << 30, 1 << 30));
+
+        assert_eq!(cube.size(), Point3D((1 << 30) + 1, (1 << 30) + 1, (1 << 30) + 1));
+        assert_eq!(cube.volume(), None);
+    }
+
+    #[rstest]
+    #[case(Point3D(1, 2, 3), Some(Box3D::from_corners(Point3D(-1, 0, 1), Point3D(-1, 1, 3))))]
+    #[case(Point3D(0, 2, 3), None)]
+    #[case(Point3D(1, 2, -3), None)]
+    fn builds_from_size(#[case] size: Point3D, #[case] expected: Option<Box3D>) {
+        let cube = Box3D::from_size(Point3D(-1, 0, 1), size);
+
+        assert_eq!(cube, expected);
+        assert!(cube.is_none_or(|c| c.size() == size));
+    }
+
+    #[test]
+    fn lists_corners() {
+        let cube = Box3D::from_corners(Point3D(0, 0, 0), Point3D(2, 3, 4));
+        let corners = cube.corners();
+
+        assert_eq!(corners.iter().collect::<HashSet<_>>().len(), 8);
+        assert!(corners.iter().all(|&c| cube.contains(c) && cube.iter_surface().any(|p| p == c)));
+        assert_eq!((corners[0], corners[6]), (cube.min(), cube.max()));
+        assert_eq!((cube.width(), cube.height(), cube.depth()), (3, 4, 5));
+    }
+
+    #[test]
+    fn iterates_surface() {
+        let cube = Box3D::from_corners(Point3D(0, 0, 0), Point3D(2, 2, 2));
+        assert_eq!(cube.iter_surface().count(), 26);
+    }
+
+    #[test]
+    fn intersects_and_unites() {
+        let a = Box3D::from_corners(Point3D(0, 0, 0), Point3D(4, 4, 4));
+        let b = Box3D::from_corners(Point3D(3, 3, 3), Point3D(6, 6, 6));
+
+        assert_eq!(a.intersection(&b), Some(Box3D::from_corners(Point3D(3, 3, 3), Point3D(4, 4, 4))));
+        assert_eq!(a.union(&b), Box3D::from_corners(Point3D(0, 0, 0), Point3D(6, 6, 6)));
+        assert_eq!(a.intersection(&Box3D::from_corners(Point3D(5, 0, 0), Point3D(6, 1, 1))), None);
+    }
+
+    #[rstest]
+    #[case(Box3D::from_corners(Point3D(1, 1, 1), Point3D(2, 2, 2)), 6)]
+    #[case(Box3D::from_corners(Point3D(-1, -1, -1), Point3D(9, 9, 2)), 1)]
+    #[case(Box3D::from_corners(Point3D(9, 9, 9), Point3D(10, 10, 10)), 1)]
+    fn subtracts_into_disjoint_pieces(#[case] cut: Box3D, #[case] piece_count: usize) {
+        let cube = Box3D::from_corners(Point3D(0, 0, 0), Point3D(3, 3, 3));
+        let pieces = cube.subtract(&cut);
+
+        assert_eq!(pieces.len(), piece_count);
+        assert_eq!(pieces.iter().map(|p| p.volume().unwrap()).sum::<usize>(), cube.iter_points().filter(|&p| !cut.contains(p)).count());
+        let points = pieces.iter().flat_map(|p| p.iter_points().collect::<Vec<_>>()).collect::<HashSet<_>>();
+        assert!(points.iter().all(|&p| cube.contains(p) && !cut.contains(p)));
+    }
+}
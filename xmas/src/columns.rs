@@ -0,0 +1,212 @@
+use std::{ops::Range, str::FromStr};
+use thiserror::Error;
+
+use crate::{
+    map2d::{ByteMap, PaddedMap, PaddingOptions, ParseMapError},
+    parse::{ParseError, ParseErrorKind, ParseResult},
+    point2d::Point2D,
+    rect2d::Rect2D,
+};
+
+/// Blocks of text separated by columns that are all spaces, like worksheets where each problem
+/// takes a few columns. Short lines are treated as if padded with spaces. Fails on non-ASCII
+/// text, where bytes and columns wouldn't line up.
+pub fn column_blocks(text: &str) -> Result<Vec<ColumnBlock>, ColumnBlocksError> {
+    if let Some(offset) = text.find(|ch: char| !ch.is_ascii()) {
+        let kind = ParseErrorKind::Expected("ASCII text".to_string());
+        return Err(ColumnBlocksError::NonAscii(ParseError::at(text, offset, kind)));
+    }
+
+    let options = PaddingOptions::new(b' ').trim_trailing_empty_lines(true);
+    let PaddedMap { map: grid, .. } = ByteMap::parse_padded(text, &options)?;
+    let is_separator = |x: usize| grid.column(x).all(|&b| b == b' ');
+
+    let mut blocks = Vec::new();
+    let mut start = None;
+    for x in 0..=grid.width() {
+        match (start, x < grid.width() && !is_separator(x)) {
+            (None, true) => start = Some(x),
+            (Some(from), false) => {
+                blocks.push(ColumnBlock::from_grid(&grid, from..x, 0..grid.height()));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+    Ok(blocks)
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ColumnBlocksError {
+    #[error(transparent)]
+    Map(#[from] ParseMapError),
+    #[error(transparent)]
+    NonAscii(ParseError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnOrder {
+    LeftToRight,
+    RightToLeft,
+}
+
+/// Rectangular piece of a text block, remembering where it was for error reporting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnBlock {
+    x: usize,
+    y: usize,
+    grid: ByteMap,
+}
+
+impl ColumnBlock {
+    fn from_grid(grid: &ByteMap, columns: Range<usize>, rows: Range<usize>) -> Self {
+        let from = Point2D(columns.start as isize, rows.start as isize);
+        let size = Point2D(columns.len() as isize, rows.len() as isize);
+        let grid = match Rect2D::from_size(from, size) {
+            Some(rect) => grid.crop(rect),
+            None => ByteMap::new_with_default_tiles(size),
+        };
+        Self { x: columns.start, y: rows.start, grid }
+    }
+
+    /// Column of the original text where the block starts, 0-based.
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// Line of the original text where the block starts, 0-based.
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    pub fn grid(&self) -> &ByteMap {
+        &self.grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    pub fn row_text(&self, y: usize) -> &str {
+        // Only ASCII text is split into blocks
+        std::str::from_utf8(self.grid.row(y)).unwrap()
+    }
+
+    /// Sub-block with only some of the rows, like everything but an operator line.
+    pub fn rows(&self, rows: Range<usize>) -> Self {
+        let mut block = Self::from_grid(&self.grid, 0..self.width(), rows);
+        block.x += self.x;
+        block.y += self.y;
+        block
+    }
+
+    /// A number per non-blank row, read left to right.
+    pub fn numbers_by_row<T: FromStr>(&self) -> ParseResult<Vec<T>> {
+        (0..self.height())
+            .filter(|&y| !self.row_text(y).trim().is_empty())
+            .map(|y| {
+                let text = self.row_text(y);
+                let offset = text.len() - text.trim_start().len();
+                self.parse_number(text.trim(), Point2D(offset as isize, y as isize))
+            })
+            .collect()
+    }
+
+    /// A number per non-blank column, its digits read top to bottom.
+    pub fn numbers_by_column<T: FromStr>(&self, order: ColumnOrder) -> ParseResult<Vec<T>> {
+        let mut columns = (0..self.width()).collect::<Vec<_>>();
+        if order == ColumnOrder::RightToLeft {
+            columns.reverse();
+        }
+
+        columns
+            .into_iter()
+            .filter_map(|x| {
+                let digits = self.grid.column(x).map(|&b| b as char).filter(|&ch| ch != ' ').collect::<String>();
+                let first_y = self.grid.column(x).position(|&b| b != b' ')?;
+                Some(self.parse_number(&digits, Point2D(x as isize, first_y as isize)))
+            })
+            .collect()
+    }
+
+    fn parse_number<T: FromStr>(&self, s: &str, at: Point2D) -> ParseResult<T> {
+        s.parse().map_err(|_| ParseError {
+            line: self.y + at.1 as usize + 1,
+            column: self.x + at.0 as usize + 1,
+            kind: ParseErrorKind::InvalidInteger(s.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const WORKSHEET: &str = concat!(
+        "123 328  51 64 \n",
+        " 45 64  387 23 \n",
+        "  6 98  215 314\n",
+        "*   +   *   +  \n",
+    );
+
+    #[test]
+    fn splits_on_blank_columns() {
+        let blocks = column_blocks(WORKSHEET).unwrap();
+
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks.iter().map(|b| b.x()).collect::<Vec<_>>(), vec![0, 4, 8, 12]);
+        assert_eq!(blocks[1].row_text(3), "+  ");
+    }
+
+    #[rstest]
+    #[case(0, vec![123, 45, 6])]
+    #[case(3, vec![64, 23, 314])]
+    fn reads_rows(#[case] block: usize, #[case] expected: Vec<u64>) {
+        let blocks = column_blocks(WORKSHEET).unwrap();
+        assert_eq!(blocks[block].rows(0..3).numbers_by_row(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case(ColumnOrder::RightToLeft, vec![4, 431, 623])]
+    #[case(ColumnOrder::LeftToRight, vec![623, 431, 4])]
+    fn reads_columns(#[case] order: ColumnOrder, #[case] expected: Vec<u64>) {
+        let blocks = column_blocks(WORKSHEET).unwrap();
+        assert_eq!(blocks[3].rows(0..3).numbers_by_column(order), Ok(expected));
+    }
+
+    #[test]
+    fn pads_ragged_lines() {
+        let blocks = column_blocks("1 23\n4 5\n\n").unwrap();
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1].height(), 2);
+        assert_eq!(blocks[1].numbers_by_column(ColumnOrder::LeftToRight), Ok(vec![25, 3]));
+    }
+
+    #[test]
+    fn fails_on_empty_text() {
+        assert_eq!(column_blocks("\n\n"), Err(ColumnBlocksError::Map(ParseMapError::EmptyString)));
+    }
+
+    #[test]
+    fn fails_on_non_ascii_text() {
+        let Err(ColumnBlocksError::NonAscii(error)) = column_blocks("1 2\n3 é\n") else {
+            panic!("expected a non-ASCII error");
+        };
+
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn reports_position_of_invalid_numbers() {
+        let blocks = column_blocks("12 3\n4x 5\n").unwrap();
+        let error = blocks[0].numbers_by_row::<u32>().unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// States of a simulation up to the first repeat. The state at `start + period` is the same as
+/// the one at `start`, so any later step can be looked up without simulating it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    start: usize,
+    period: usize,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Amount of steps before the cycle begins.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// The states that were simulated, from the initial one to the first repeat.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    pub fn index_at(&self, step: usize) -> usize {
+        if step < self.states.len() {
+            step
+        } else {
+            self.start + (step - self.start) % self.period
+        }
+    }
+
+    pub fn state_at(&self, step: usize) -> &S {
+        &self.states[self.index_at(step)]
+    }
+
+    /// Value at `step` of something that grows by the same amount every period, like a height
+    /// that isn't part of the state.
+    pub fn extrapolate(&self, step: usize, value: impl Fn(&S) -> i64) -> i64 {
+        if step < self.states.len() {
+            return value(&self.states[step]);
+        }
+
+        let cycles = ((step - self.start) / self.period) as i64;
+        let growth = value(&self.states[self.start + self.period]) - value(&self.states[self.start]);
+        value(self.state_at(step)) + cycles * growth
+    }
+}
+
+/// Steps until a state comes back, remembering every state on the way.
+pub fn find_cycle<S>(initial: S, step: impl FnMut(&S) -> S) -> Cycle<S>
+    where S: Clone + Eq + Hash
+{
+    find_cycle_by_key(initial, step, S::clone)
+}
+
+/// Like [`find_cycle`], but two states count as the same when their keys are. Useful when the
+/// state has parts that only ever grow, see [`Cycle::extrapolate`].
+pub fn find_cycle_by_key<S, K>(initial: S, mut step: impl FnMut(&S) -> S, key: impl Fn(&S) -> K) -> Cycle<S>
+    where K: Eq + Hash
+{
+    let mut seen = HashMap::new();
+    seen.insert(key(&initial), 0);
+    let mut states = vec![initial];
+
+    loop {
+        let next = step(states.last().unwrap());
+        let next_key = key(&next);
+        states.push(next);
+
+        if let Some(&start) = seen.get(&next_key) {
+            return Cycle { start, period: states.len() - 1 - start, states };
+        }
+        seen.insert(next_key, states.len() - 1);
+    }
+}
+
+/// Brent's algorithm. Only keeps a couple of states around, for when they are too big to store,
+/// at the cost of running the simulation about twice. Returns the start and period of the cycle.
+pub fn find_cycle_brent<S>(initial: S, mut step: impl FnMut(&S) -> S) -> (usize, usize)
+    where S: Clone + Eq
+{
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (start, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bitset::BitSet, direction::QuarterRotation, map2d::ByteMap};
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn next(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[rstest]
+    #[case(0, 2, 6)]
+    #[case(26, 0, 6)]
+    #[case(7, 0, 1)]
+    fn finds_start_and_period(#[case] initial: u32, #[case] start: usize, #[case] period: usize) {
+        let step = |x: &u32| if *x == 7 { 7 } else { next(x) };
+        let cycle = find_cycle(initial, step);
+
+        assert_eq!((cycle.start(), cycle.period()), (start, period));
+        assert_eq!(find_cycle_brent(initial, step), (start, period));
+    }
+
+    #[test]
+    fn looks_up_far_steps() {
+        let cycle = find_cycle(0, next);
+
+        let mut state = 0;
+        for n in 0..1000 {
+            assert_eq!(*cycle.state_at(n), state);
+            state = next(&state);
+        }
+    }
+
+    #[test]
+    fn extrapolates_growing_values() {
+        // Position on a 5-long loop plus the amount of laps done
+        let step = |&(pos, laps): &(u32, i64)| if pos == 4 { (0, laps + 1) } else { (pos + 1, laps) };
+        let cycle = find_cycle_by_key((2, 0), step, |&(pos, _)| pos);
+
+        assert_eq!(cycle.period(), 5);
+        assert_eq!(cycle.extrapolate(1_000_000_003, |&(_, laps)| laps), 200_000_001);
+    }
+
+    #[test]
+    fn works_with_bitsets() {
+        let rotate = |set: &BitSet| (0..set.len()).map(|i| set.get((i + 1) % set.len())).collect::<BitSet>();
+        let cycle = find_cycle(BitSet::from_indices(6, [0, 3]), rotate);
+
+        assert_eq!((cycle.start(), cycle.period()), (0, 3));
+    }
+
+    #[test]
+    fn works_with_maps() {
+        let map = ByteMap::from_str("ab\ncd\n").unwrap();
+        let cycle = find_cycle(map.clone(), |m| m.rotate(QuarterRotation::Right));
+
+        assert_eq!(cycle.period(), 4);
+        assert_eq!(cycle.state_at(1_000_000_000), &map);
+    }
+}
@@ -2,6 +2,7 @@ use crate::point2d::Point2D;
 use enum_map::Enum;
 use Direction::*;
 
+/// Directions on screen, matching [`Point2D`]: `Up` is negative y and `Right` is positive x.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Enum)]
 pub enum Direction {
     Up,
@@ -61,6 +62,78 @@ impl From<Direction> for Point2D {
     }
 }
 
+/// Directions on screen including diagonals, in clockwise order from `Up`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Enum)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn as_point(&self) -> Point2D {
+        DIRECTIONS_8[*self as usize]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+
+    /// Turns by 45° steps, clockwise when positive.
+    pub fn turn(&self, eighths: isize) -> Self {
+        Self::ALL[(*self as isize + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_quarter(&self, rot: QuarterRotation) -> Self {
+        self.turn(rot.value() as isize * 2)
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.turn(4)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        Self::ALL[value.value() as usize * 2]
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = &'static str;
+
+    fn try_from(value: Direction8) -> Result<Self, Self::Error> {
+        if value.is_diagonal() {
+            Err("Diagonals have no matching Direction")
+        } else {
+            Ok(DIRECTIONS[value as usize / 2])
+        }
+    }
+}
+
+impl From<Direction8> for Point2D {
+    fn from(value: Direction8) -> Self {
+        value.as_point()
+    }
+}
+
+/// Turn as seen on screen, `Right` being clockwise.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum QuarterRotation {
     None,
@@ -79,3 +152,75 @@ impl QuarterRotation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const ROTATIONS: [QuarterRotation; 4] = [
+        QuarterRotation::None,
+        QuarterRotation::Right,
+        QuarterRotation::TurnAround,
+        QuarterRotation::Left,
+    ];
+
+    #[rstest]
+    #[case(Up, Point2D(0, -1))]
+    #[case(Right, Point2D(1, 0))]
+    #[case(Down, Point2D(0, 1))]
+    #[case(Left, Point2D(-1, 0))]
+    fn follows_screen_coordinates(#[case] direction: Direction, #[case] expected: Point2D) {
+        assert_eq!(direction.as_point(), expected);
+        assert_eq!(expected.try_get_direction(), Some((direction, 1)));
+    }
+
+    #[rstest]
+    fn inverse_is_an_involution(#[values(Up, Right, Down, Left)] direction: Direction) {
+        assert_eq!(direction.inverse().inverse(), direction);
+        assert_eq!(direction.as_point() + direction.inverse().as_point(), Point2D::ZERO);
+    }
+
+    #[rstest]
+    fn turning_right_is_clockwise(#[values(Up, Right, Down, Left)] direction: Direction) {
+        let Point2D(x, y) = direction.as_point();
+        assert_eq!(direction.turn(QuarterRotation::Right).as_point(), Point2D(-y, x));
+    }
+
+    #[test]
+    fn eight_directions_follow_screen_coordinates() {
+        for (i, direction) in Direction8::ALL.into_iter().enumerate() {
+            assert_eq!(direction.as_point(), DIRECTIONS_8[i]);
+            assert_eq!(direction.inverse().inverse(), direction);
+            assert_eq!(direction.as_point() + direction.inverse().as_point(), Point2D::ZERO);
+            assert_eq!(direction.turn(1).turn(-1), direction);
+            assert_eq!(direction.turn(8), direction);
+        }
+
+        assert_eq!(Direction8::UpRight.as_point(), Up.combined(Right));
+        assert_eq!(Direction8::UpLeft.turn(1), Direction8::Up);
+    }
+
+    #[rstest]
+    fn converts_between_four_and_eight_directions(#[values(Up, Right, Down, Left)] direction: Direction) {
+        let eight = Direction8::from(direction);
+
+        assert_eq!(eight.as_point(), direction.as_point());
+        assert_eq!(Direction::try_from(eight), Ok(direction));
+        assert_eq!(Direction::try_from(eight.turn(1)).ok(), None);
+        assert_eq!(eight.turn_quarter(QuarterRotation::Right), direction.turn(QuarterRotation::Right).into());
+    }
+
+    #[rstest]
+    fn turns_compose(#[values(Up, Right, Down, Left)] direction: Direction) {
+        for (i, &a) in ROTATIONS.iter().enumerate() {
+            for (j, &b) in ROTATIONS.iter().enumerate() {
+                let combined = ROTATIONS[(i + j) % ROTATIONS.len()];
+                assert_eq!(direction.turn(a).turn(b), direction.turn(combined));
+            }
+        }
+
+        assert_eq!(direction.turn(QuarterRotation::Right).turn(QuarterRotation::Left), direction);
+        assert_eq!(direction.turn(QuarterRotation::TurnAround), direction.inverse());
+    }
+}
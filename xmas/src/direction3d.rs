@@ -2,6 +2,7 @@ use crate::point3d::Point3D;
 use enum_map::Enum;
 use Direction3D::*;
 
+/// Same screen convention as [`crate::direction::Direction`], with `Front` being positive z.
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Enum)]
 pub enum Direction3D {
     Up,
@@ -12,16 +13,15 @@ pub enum Direction3D {
     Back,
 }
 
-
 pub const DIRECTIONS_3D: [Direction3D; 6] = [Up, Left, Down, Right, Front, Back];
 
 impl Direction3D {
     pub fn as_point(&self) -> Point3D {
         match self {
             Up => Point3D(0, -1, 0),
-            Left => Point3D(1, 0, 0),
+            Left => Point3D(-1, 0, 0),
             Down => Point3D(0, 1, 0),
-            Right => Point3D(-1, 0, 0),
+            Right => Point3D(1, 0, 0),
             Front => Point3D(0, 0, 1),
             Back => Point3D(0, 0, -1),
         }
@@ -55,9 +55,51 @@ impl TryFrom<Point3D> for Direction3D {
     fn try_from(value: Point3D) -> Result<Self, Self::Error> {
         Ok(match value {
             Point3D(x, 0, 0) if x != 0 => if x > 0 { Direction3D::Right } else { Direction3D::Left },
-            Point3D(0, y, 0) if y != 0 => if y > 0 { Direction3D::Up } else { Direction3D::Down },
+            Point3D(0, y, 0) if y != 0 => if y > 0 { Direction3D::Down } else { Direction3D::Up },
             Point3D(0, 0, z) if z != 0 => if z > 0 { Direction3D::Front } else { Direction3D::Back },
             _ => return Err("Cannot convert vector to direction"),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{direction::Direction, point2d::Point2D};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Point3D(5, 0, 0), Ok(Right))]
+    #[case(Point3D(0, 2, 0), Ok(Down))]
+    #[case(Point3D(0, -2, 0), Ok(Up))]
+    #[case(Point3D(0, 0, -1), Ok(Back))]
+    #[case(Point3D(1, 1, 0), Err("Cannot convert vector to direction"))]
+    fn converts_from_points(#[case] point: Point3D, #[case] expected: Result<Direction3D, &str>) {
+        assert_eq!(Direction3D::try_from(point), expected);
+    }
+
+    #[test]
+    fn points_round_trip() {
+        for direction in DIRECTIONS_3D {
+            assert_eq!(Direction3D::try_from(direction.as_point()), Ok(direction));
+        }
+    }
+
+    #[test]
+    fn inverse_is_an_involution() {
+        for direction in DIRECTIONS_3D {
+            assert_eq!(direction.inverse().inverse(), direction);
+            assert_eq!(direction.as_point() + direction.inverse().as_point(), Point3D::ZERO);
+        }
+    }
+
+    #[rstest]
+    #[case(Up, Direction::Up)]
+    #[case(Right, Direction::Right)]
+    #[case(Down, Direction::Down)]
+    #[case(Left, Direction::Left)]
+    fn matches_2d_directions(#[case] direction: Direction3D, #[case] flat: Direction) {
+        let Point3D(x, y, z) = direction.as_point();
+        assert_eq!((Point2D(x, y), z), (flat.as_point(), 0));
+    }
+}
@@ -0,0 +1,130 @@
+use std::{collections::{HashMap, HashSet}, fmt::{Display, Write}, path::Path};
+
+use crate::graph::Graph;
+
+/// Settings for rendering a [`Graph`] as Graphviz DOT.
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    pub path: Vec<usize>,
+    pub colors: HashMap<usize, String>,
+    pub max_nodes: Option<usize>,
+}
+
+impl DotOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_path(mut self, path: impl IntoIterator<Item = usize>) -> Self {
+        self.path = path.into_iter().collect();
+        self
+    }
+
+    pub fn with_color(mut self, node: usize, color: impl Into<String>) -> Self {
+        self.colors.insert(node, color.into());
+        self
+    }
+
+    /// Only the nodes with the lowest indices are kept, which for explored graphs are the closest to the start.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+}
+
+impl<N: Display> Graph<N> {
+    pub fn to_dot(&self, options: &DotOptions) -> String {
+        let node_count = options.max_nodes.map_or(self.len(), |max| max.min(self.len()));
+        let path_nodes = options.path.iter().copied().collect::<HashSet<_>>();
+        let path_edges = options.path
+            .windows(2)
+            .map(|w| (w[0], w[1]))
+            .collect::<HashSet<_>>();
+
+        let mut dot = String::from("digraph {\n");
+        if node_count < self.len() {
+            writeln!(dot, "    label=\"Showing {} of {} nodes\";", node_count, self.len()).unwrap();
+        }
+
+        for index in 0..node_count {
+            let mut attributes = vec![format!("label=\"{}\"", escape(&self.node(index).to_string()))];
+            if let Some(color) = options.colors.get(&index) {
+                attributes.push(format!("style=filled, fillcolor=\"{}\"", escape(color)));
+            }
+            if path_nodes.contains(&index) {
+                attributes.push("penwidth=2".to_string());
+            }
+            writeln!(dot, "    n{} [{}];", index, attributes.join(", ")).unwrap();
+        }
+
+        for (from, to) in self.edges().filter(|&(from, to)| from < node_count && to < node_count) {
+            if path_edges.contains(&(from, to)) {
+                writeln!(dot, "    n{} -> n{} [color=red, penwidth=2];", from, to).unwrap();
+            } else {
+                writeln!(dot, "    n{} -> n{};", from, to).unwrap();
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    pub fn write_dot(&self, path: impl AsRef<Path>, options: &DotOptions) -> std::io::Result<()> {
+        std::fs::write(path, self.to_dot(options))
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> Graph<String> {
+        "svr: aaa bbb\naaa: out\nbbb: out\n".parse().unwrap()
+    }
+
+    #[test]
+    fn writes_nodes_and_edges() {
+        let dot = sample_graph().to_dot(&DotOptions::new());
+
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("n0 [label=\"svr\"];"));
+        assert!(dot.contains("n3 [label=\"out\"];"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n2 -> n3;"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn highlights_path_and_colors() {
+        let options = DotOptions::new()
+            .with_path([0, 1, 3])
+            .with_color(3, "green");
+        let dot = sample_graph().to_dot(&options);
+
+        assert!(dot.contains("n0 -> n1 [color=red, penwidth=2];"));
+        assert!(dot.contains("n1 -> n3 [color=red, penwidth=2];"));
+        assert!(dot.contains("n0 -> n2;"));
+        assert!(dot.contains("n3 [label=\"out\", style=filled, fillcolor=\"green\", penwidth=2];"));
+    }
+
+    #[test]
+    fn caps_node_count() {
+        let dot = sample_graph().to_dot(&DotOptions::new().with_max_nodes(2));
+
+        assert!(dot.contains("label=\"Showing 2 of 4 nodes\";"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(!dot.contains("n2"));
+        assert!(!dot.contains("n3"));
+    }
+
+    #[test]
+    fn escapes_labels() {
+        let mut graph = Graph::new();
+        graph.add_node("say \"hi\"".to_string());
+        assert!(graph.to_dot(&DotOptions::new()).contains(r#"label="say \"hi\"""#));
+    }
+}
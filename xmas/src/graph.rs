@@ -0,0 +1,494 @@
+use std::{borrow::Borrow, collections::{HashMap, HashSet, VecDeque}, hash::Hash, str::FromStr};
+use thiserror::Error;
+
+/// Directed graph whose nodes are addressed by the index they were inserted with.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self { nodes: Vec::new(), indices: HashMap::new(), edges: Vec::new() }
+    }
+}
+
+impl<N: Clone + Hash + Eq> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the index of the node, inserting it first if it's not in the graph yet.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        index
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N) -> (usize, usize) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.add_edge_by_index(from, to);
+        (from, to)
+    }
+
+    /// Builds the state graph a search would walk, breadth first from `start`.
+    /// Once `max_nodes` is reached no new states are added, only edges between the known ones.
+    pub fn explore<I>(start: N, mut successors: impl FnMut(&N) -> I, max_nodes: Option<usize>) -> Self
+        where I: IntoIterator<Item = N>
+    {
+        let mut graph = Self::new();
+        graph.add_node(start);
+
+        let mut next_to_expand = 0;
+        while next_to_expand < graph.len() {
+            let from = next_to_expand;
+            next_to_expand += 1;
+
+            for state in successors(graph.node(from)) {
+                let to = match graph.index_of(&state) {
+                    Some(index) => index,
+                    None if max_nodes.is_some_and(|max| graph.len() >= max) => continue,
+                    None => graph.add_node(state),
+                };
+                graph.add_edge_by_index(from, to);
+            }
+        }
+
+        graph
+    }
+
+    pub fn index_of<Q>(&self, node: &Q) -> Option<usize>
+        where N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized
+    {
+        self.indices.get(node).copied()
+    }
+}
+
+impl<N> Graph<N> {
+    pub fn add_edge_by_index(&mut self, from: usize, to: usize) {
+        assert!(to < self.nodes.len(), "edge target {to} is not a node");
+        self.edges[from].push(to);
+    }
+
+    pub fn node(&self, index: usize) -> &N {
+        &self.nodes[index]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    pub fn successors(&self, index: usize) -> &[usize] {
+        &self.edges[index]
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .flat_map(|(from, to)| to.iter().map(move |&to| (from, to)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn in_degrees(&self) -> Vec<usize> {
+        let mut degrees = vec![0; self.len()];
+        for (_, to) in self.edges() {
+            degrees[to] += 1;
+        }
+        degrees
+    }
+
+    /// Kahn's algorithm. On failure the error holds one of the cycles that prevented the sort.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, CycleError> {
+        let mut degrees = self.in_degrees();
+        let mut queue = (0..self.len())
+            .filter(|&i| degrees[i] == 0)
+            .collect::<VecDeque<_>>();
+
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(index) = queue.pop_front() {
+            order.push(index);
+            for &next in self.successors(index) {
+                degrees[next] -= 1;
+                if degrees[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            let cycle = self.find_cycle().expect("unsorted nodes must contain a cycle");
+            Err(CycleError { cycle })
+        }
+    }
+
+    pub fn is_acyclic(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// Returns the nodes of a cycle in edge order, the last one pointing back to the first.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        let mut states = vec![VisitState::Unvisited; self.len()];
+        (0..self.len()).find_map(|root| self.depth_first(root, &mut states, &mut Vec::new()).err())
+    }
+
+    /// Like [`Graph::topological_sort`], but only for the nodes reachable from `from`, so cycles
+    /// elsewhere in the graph don't matter.
+    pub fn topological_sort_from(&self, from: usize) -> Result<Vec<usize>, CycleError> {
+        let mut states = vec![VisitState::Unvisited; self.len()];
+        let mut order = Vec::new();
+        self.depth_first(from, &mut states, &mut order).map_err(|cycle| CycleError { cycle })?;
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Iterative DFS from `root` that pushes nodes to `finished` once all their successors are done.
+    /// Stops at the first cycle found and returns it.
+    fn depth_first(&self, root: usize, states: &mut [VisitState], finished: &mut Vec<usize>) -> Result<(), Vec<usize>> {
+        if states[root] != VisitState::Unvisited {
+            return Ok(());
+        }
+
+        let mut path: Vec<(usize, usize)> = vec![(root, 0)];
+        states[root] = VisitState::InPath;
+        while let Some((node, next_edge)) = path.last_mut() {
+            let node = *node;
+            let Some(&next) = self.edges[node].get(*next_edge) else {
+                states[node] = VisitState::Done;
+                finished.push(node);
+                path.pop();
+                continue;
+            };
+
+            *next_edge += 1;
+            match states[next] {
+                VisitState::Unvisited => {
+                    states[next] = VisitState::InPath;
+                    path.push((next, 0));
+                },
+                VisitState::InPath => {
+                    let start = path.iter().position(|&(n, _)| n == next).unwrap();
+                    return Err(path[start..].iter().map(|&(n, _)| n).collect());
+                },
+                VisitState::Done => {},
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tarjan's algorithm, done iteratively so big inputs can't overflow the stack.
+    /// Components are returned in topological order: edges only go from a component to a later one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let mut indices = vec![UNVISITED; self.len()];
+        let mut lowlinks = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        for root in 0..self.len() {
+            if indices[root] != UNVISITED {
+                continue;
+            }
+
+            let mut call_stack = vec![(root, 0)];
+            while let Some((node, next_edge)) = call_stack.last_mut() {
+                let node = *node;
+                if indices[node] == UNVISITED {
+                    indices[node] = counter;
+                    lowlinks[node] = counter;
+                    counter += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&next) = self.edges[node].get(*next_edge) {
+                    *next_edge += 1;
+                    if indices[next] == UNVISITED {
+                        call_stack.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlinks[node] = lowlinks[node].min(indices[next]);
+                    }
+                    continue;
+                }
+
+                call_stack.pop();
+                if let Some(&(parent, _)) = call_stack.last() {
+                    lowlinks[parent] = lowlinks[parent].min(lowlinks[node]);
+                }
+
+                if lowlinks[node] == indices[node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components.reverse();
+        components
+    }
+
+    /// Collapses every strongly connected component into a single node, which always gives a DAG.
+    pub fn condensation(&self) -> Condensation {
+        let components = self.strongly_connected_components();
+        let mut component_of = vec![0; self.len()];
+        for (i, component) in components.iter().enumerate() {
+            for &node in component {
+                component_of[node] = i;
+            }
+        }
+
+        let mut graph = Graph::new();
+        for i in 0..components.len() {
+            graph.add_node(i);
+        }
+
+        let mut added = HashSet::new();
+        for (from, to) in self.edges() {
+            let edge = (component_of[from], component_of[to]);
+            if edge.0 != edge.1 && added.insert(edge) {
+                graph.add_edge_by_index(edge.0, edge.1);
+            }
+        }
+
+        Condensation { components, component_of, graph }
+    }
+
+    /// Counts every distinct path between two nodes, parallel edges counting as different paths.
+    /// Fails if a cycle can be reached from `from`, or if there are too many paths to count.
+    pub fn count_paths(&self, from: usize, to: usize) -> Result<u128, CountPathsError> {
+        let order = self.topological_sort_from(from)?;
+        // `None` once a count overflowed, which only matters if it reaches `to`
+        let mut paths = vec![Some(0u128); self.len()];
+        paths[from] = Some(1);
+        for node in order {
+            if paths[node] == Some(0) {
+                continue;
+            }
+
+            for &next in self.successors(node) {
+                paths[next] = paths[next].zip(paths[node]).and_then(|(a, b)| a.checked_add(b));
+            }
+        }
+        paths[to].ok_or(CountPathsError::Overflow)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Condensation {
+    pub components: Vec<Vec<usize>>,
+    pub component_of: Vec<usize>,
+    pub graph: Graph<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    Unvisited,
+    InPath,
+    Done,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("Graph contains a cycle of {} nodes", cycle.len())]
+pub struct CycleError {
+    pub cycle: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum CountPathsError {
+    #[error(transparent)]
+    Cycle(#[from] CycleError),
+    #[error("Too many paths to count")]
+    Overflow,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ParseGraphError {
+    #[error("Missing ':' separator on line {0}")]
+    MissingSeparator(usize),
+}
+
+/// Parses adjacency lists in the `node: other another` format.
+impl FromStr for Graph<String> {
+    type Err = ParseGraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = Self::new();
+        for (i, line) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let (id, others) = line.split_once(':').ok_or(ParseGraphError::MissingSeparator(i + 1))?;
+            let from = graph.add_node(id.trim().to_string());
+            for other in others.split_whitespace() {
+                let to = graph.add_node(other.to_string());
+                graph.add_edge_by_index(from, to);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn graph_from_edges(edges: &[(u8, u8)]) -> Graph<u8> {
+        let mut graph = Graph::new();
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    fn assert_is_cycle(graph: &Graph<u8>, cycle: &[usize]) {
+        assert!(!cycle.is_empty());
+        for (i, &node) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(graph.successors(node).contains(&next));
+        }
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let graph = graph_from_edges(&[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+        let order = graph.topological_sort().unwrap();
+
+        assert_eq!(order.len(), graph.len());
+        for (from, to) in graph.edges() {
+            let from_pos = order.iter().position(|&n| n == from).unwrap();
+            let to_pos = order.iter().position(|&n| n == to).unwrap();
+            assert!(from_pos < to_pos);
+        }
+    }
+
+    #[rstest]
+    #[case(&[(0, 0)])]
+    #[case(&[(0, 1), (1, 0)])]
+    #[case(&[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)])]
+    fn topological_sort_reports_cycle(#[case] edges: &[(u8, u8)]) {
+        let graph = graph_from_edges(edges);
+        let error = graph.topological_sort().unwrap_err();
+
+        assert_is_cycle(&graph, &error.cycle);
+        assert!(!graph.is_acyclic());
+    }
+
+    #[test]
+    fn finds_strongly_connected_components() {
+        let graph = graph_from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (5, 4)]);
+        let components = graph.strongly_connected_components()
+            .into_iter()
+            .map(|c| {
+                let mut nodes = c.iter().map(|&i| *graph.node(i)).collect::<Vec<_>>();
+                nodes.sort();
+                nodes
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(components.len(), 3);
+        assert!(components.contains(&vec![0, 1, 2]));
+        assert!(components.contains(&vec![3, 4]));
+        assert!(components.contains(&vec![5]));
+    }
+
+    #[test]
+    fn condensation_is_acyclic() {
+        let graph = graph_from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (1, 3)]);
+        let condensation = graph.condensation();
+
+        assert_eq!(condensation.graph.len(), 2);
+        assert_eq!(condensation.graph.edges().count(), 1);
+        assert!(condensation.graph.is_acyclic());
+        assert_eq!(condensation.component_of[0], condensation.component_of[2]);
+        assert_ne!(condensation.component_of[0], condensation.component_of[3]);
+    }
+
+    #[test]
+    fn counts_paths() {
+        const INPUT: &str = concat!(
+            "you: bbb ccc\n",
+            "bbb: ddd eee\n",
+            "ccc: ddd eee fff\n",
+            "ddd: ggg\n",
+            "eee: out\n",
+            "fff: out\n",
+            "ggg: out\n",
+        );
+
+        let graph = Graph::from_str(INPUT).unwrap();
+        let from = graph.index_of("you").unwrap();
+        let to = graph.index_of("out").unwrap();
+        assert_eq!(graph.count_paths(from, to), Ok(5));
+    }
+
+    #[test]
+    fn counting_paths_ignores_unreachable_cycles() {
+        let graph = graph_from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 5), (5, 4), (5, 3)]);
+
+        assert_eq!(graph.count_paths(0, 3), Ok(2));
+        assert_eq!(graph.topological_sort_from(0).map(|o| o.len()), Ok(4));
+        let Err(CountPathsError::Cycle(error)) = graph.count_paths(4, 3) else {
+            panic!("expected a cycle");
+        };
+        assert_is_cycle(&graph, &error.cycle);
+    }
+
+    #[test]
+    fn counting_paths_reports_overflow() {
+        // Each layer doubles the paths, 2^130 doesn't fit
+        let mut graph = Graph::new();
+        for layer in 0..130u8 {
+            graph.add_edge(layer, layer + 1);
+            graph.add_edge(layer, layer + 1);
+        }
+
+        assert_eq!(graph.count_paths(0, 127), Ok(1 << 127));
+        assert_eq!(graph.count_paths(0, 130), Err(CountPathsError::Overflow));
+    }
+
+    #[rstest]
+    #[case(None, 8)]
+    #[case(Some(3), 3)]
+    fn explores_state_space(#[case] max_nodes: Option<usize>, #[case] expected: usize) {
+        let graph = Graph::explore(0u8, |&s| [s ^ 1, s ^ 2, s ^ 4], max_nodes);
+
+        assert_eq!(graph.len(), expected);
+        assert_eq!(*graph.node(0), 0);
+        assert!(graph.edges().all(|(from, to)| (graph.node(from) ^ graph.node(to)).count_ones() == 1));
+    }
+
+    #[test]
+    fn parse_returns_missing_separator_error() {
+        let result = Graph::from_str("aaa: bbb\nbbb ccc\n");
+        assert_eq!(result.unwrap_err(), ParseGraphError::MissingSeparator(2));
+    }
+}
@@ -0,0 +1,361 @@
+use std::{fmt::Display, ops, str::FromStr};
+use thiserror::Error;
+
+use crate::{point2d::Point2D, sparse::SparseGrid};
+
+/// Hex in axial coordinates, the third cube coordinate being implied, see [`Hex::s`]. On pointy-top
+/// layouts `q` grows to the east and `r` to the south-east, on flat-top ones `q` grows to the
+/// south-east and `r` to the south.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+/// Axial offsets of the 6 neighbours, counter-clockwise.
+const NEIGHBOURS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+impl Hex {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(q: isize, r: isize) -> Self {
+        Self { q, r }
+    }
+
+    /// Cube coordinates have to add up to 0.
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Option<Self> {
+        (q + r + s == 0).then_some(Self { q, r })
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    /// Amount of steps to the origin.
+    pub fn magnitude(&self) -> usize {
+        (self.q.unsigned_abs() + self.r.unsigned_abs() + self.s().unsigned_abs()) / 2
+    }
+
+    pub fn distance(&self, other: Self) -> usize {
+        (*self - other).magnitude()
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item = Hex> + '_ {
+        NEIGHBOURS.iter().map(|&n| *self + n)
+    }
+
+    /// Hexes exactly `radius` steps away, walking around the ring.
+    pub fn ring(&self, radius: usize) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut hex = *self + NEIGHBOURS[4] * radius as isize;
+        let mut ring = Vec::with_capacity(6 * radius);
+        for direction in NEIGHBOURS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += direction;
+            }
+        }
+        ring
+    }
+
+    /// Hexes up to `radius` steps away, ring by ring from the center.
+    pub fn spiral(&self, radius: usize) -> impl Iterator<Item = Hex> + '_ {
+        (0..=radius).flat_map(|r| self.ring(r))
+    }
+}
+
+impl Display for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
+
+impl ops::Add<Hex> for Hex {
+    type Output = Hex;
+
+    fn add(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl ops::AddAssign<Hex> for Hex {
+    fn add_assign(&mut self, rhs: Hex) {
+        *self = *self + rhs
+    }
+}
+
+impl ops::Sub<Hex> for Hex {
+    type Output = Hex;
+
+    fn sub(self, rhs: Hex) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl ops::SubAssign<Hex> for Hex {
+    fn sub_assign(&mut self, rhs: Hex) {
+        *self = *self - rhs
+    }
+}
+
+impl ops::Mul<isize> for Hex {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+macro_rules! hex_directions {
+    ($(#[$meta:meta])* $name:ident: $($variant:ident => ($q:literal, $r:literal), $s:literal),*) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            /// Clockwise.
+            pub const ALL: [$name; 6] = [$($name::$variant),*];
+
+            pub fn as_hex(&self) -> Hex {
+                match self {
+                    $($name::$variant => Hex::new($q, $r)),*
+                }
+            }
+
+            /// Turns by 60° steps, clockwise when positive.
+            pub fn turn(&self, sixths: isize) -> Self {
+                Self::ALL[(*self as isize + sixths).rem_euclid(6) as usize]
+            }
+
+            pub fn inverse(&self) -> Self {
+                self.turn(3)
+            }
+        }
+
+        impl From<$name> for Hex {
+            fn from(value: $name) -> Self {
+                value.as_hex()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseHexDirectionError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.trim().to_ascii_lowercase().as_str() {
+                    $($s => Ok($name::$variant),)*
+                    _ => Err(ParseHexDirectionError(s.trim().to_string())),
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $($name::$variant => write!(f, $s)),*
+                }
+            }
+        }
+    };
+}
+
+hex_directions!(
+    /// Neighbours of hexes with a flat top and bottom edge.
+    FlatDirection:
+    N => (0, -1), "n",
+    NE => (1, -1), "ne",
+    SE => (1, 0), "se",
+    S => (0, 1), "s",
+    SW => (-1, 1), "sw",
+    NW => (-1, 0), "nw"
+);
+
+hex_directions!(
+    /// Neighbours of hexes with a pointy top and bottom corner.
+    PointyDirection:
+    NE => (1, -1), "ne",
+    E => (1, 0), "e",
+    SE => (0, 1), "se",
+    SW => (-1, 1), "sw",
+    W => (-1, 0), "w",
+    NW => (0, -1), "nw"
+);
+
+/// Parses comma separated directions like `ne,sw,s`.
+pub fn parse_path<D: FromStr<Err = ParseHexDirectionError>>(s: &str) -> Result<Vec<D>, ParseHexDirectionError> {
+    s.trim().split(',').filter(|d| !d.trim().is_empty()).map(D::from_str).collect()
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("Invalid hex direction \"{0}\"")]
+pub struct ParseHexDirectionError(pub String);
+
+/// Hexes that were set, anywhere on an unbounded grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexMap<T> {
+    grid: SparseGrid<T>,
+}
+
+impl<T> Default for HexMap<T> {
+    fn default() -> Self {
+        Self { grid: SparseGrid::new() }
+    }
+}
+
+impl<T> HexMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_tile(&self, hex: Hex) -> Option<&T> {
+        self.grid.get_tile(Self::key(hex))
+    }
+
+    pub fn get_tile_mut(&mut self, hex: Hex) -> Option<&mut T> {
+        self.grid.get_tile_mut(Self::key(hex))
+    }
+
+    /// Returns the tile that was there before, if any.
+    pub fn set_tile(&mut self, hex: Hex, tile: T) -> Option<T> {
+        self.grid.set_tile(Self::key(hex), tile)
+    }
+
+    pub fn remove_tile(&mut self, hex: Hex) -> Option<T> {
+        self.grid.remove_tile(Self::key(hex))
+    }
+
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.grid.contains(Self::key(hex))
+    }
+
+    pub fn len(&self) -> usize {
+        self.grid.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.grid.is_empty()
+    }
+
+    /// Set tiles in no particular order.
+    pub fn iter_with_points(&self) -> impl Iterator<Item = (Hex, &T)> + '_ {
+        self.grid.iter_with_points().map(|(p, t)| (Hex::new(p.0, p.1), t))
+    }
+
+    /// Neighbours of `hex` that were set.
+    pub fn neighbours(&self, hex: Hex) -> impl Iterator<Item = (Hex, &T)> + '_ {
+        NEIGHBOURS.iter().filter_map(move |&n| self.get_tile(hex + n).map(|t| (hex + n, t)))
+    }
+
+    fn key(hex: Hex) -> Point2D {
+        Point2D(hex.q, hex.r)
+    }
+}
+
+impl<T> FromIterator<(Hex, T)> for HexMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Hex, T)>>(iter: I) -> Self {
+        Self { grid: iter.into_iter().map(|(h, t)| (Self::key(h), t)).collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    #[rstest]
+    #[case("ne,ne,ne", 3, 3)]
+    #[case("ne,ne,sw,sw", 0, 2)]
+    #[case("ne,ne,s,s", 2, 2)]
+    #[case("se,sw,se,sw,sw", 3, 3)]
+    fn walks_flat_paths(#[case] path: &str, #[case] distance: usize, #[case] furthest: usize) {
+        let steps = parse_path::<FlatDirection>(path).unwrap();
+        let positions = steps
+            .iter()
+            .scan(Hex::ZERO, |hex, d| {
+                *hex += d.as_hex();
+                Some(*hex)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions.last().unwrap().magnitude(), distance);
+        assert_eq!(positions.iter().map(|h| h.magnitude()).max(), Some(furthest));
+    }
+
+    #[test]
+    fn parse_path_returns_errors() {
+        assert_eq!(parse_path::<PointyDirection>("e, NE"), Ok(vec![PointyDirection::E, PointyDirection::NE]));
+        assert_eq!(parse_path::<PointyDirection>("e,n"), Err(ParseHexDirectionError("n".to_string())));
+    }
+
+    #[test]
+    fn directions_are_neighbours() {
+        let flat = FlatDirection::ALL.map(|d| d.as_hex()).into_iter().collect::<HashSet<_>>();
+        let pointy = PointyDirection::ALL.map(|d| d.as_hex()).into_iter().collect::<HashSet<_>>();
+        let neighbours = Hex::ZERO.neighbours().collect::<HashSet<_>>();
+
+        assert_eq!(flat, neighbours);
+        assert_eq!(pointy, neighbours);
+        for d in FlatDirection::ALL {
+            assert_eq!(d.as_hex() + d.inverse().as_hex(), Hex::ZERO);
+            assert_eq!(d.turn(1).turn(-1), d);
+        }
+        assert_eq!(PointyDirection::NW.turn(1), PointyDirection::NE);
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 6)]
+    #[case(3, 18)]
+    fn builds_rings(#[case] radius: usize, #[case] expected: usize) {
+        let center = Hex::new(2, -7);
+        let ring = center.ring(radius);
+
+        assert_eq!(ring.len(), expected);
+        assert!(ring.iter().all(|h| h.distance(center) == radius));
+        assert_eq!(ring.iter().collect::<HashSet<_>>().len(), expected);
+    }
+
+    #[test]
+    fn spirals_outwards() {
+        let spiral = Hex::ZERO.spiral(3).collect::<Vec<_>>();
+
+        assert_eq!(spiral.len(), 37);
+        assert_eq!(spiral[0], Hex::ZERO);
+        assert!(spiral.windows(2).all(|w| w[0].magnitude() <= w[1].magnitude()));
+    }
+
+    #[test]
+    fn converts_cube_coordinates() {
+        assert_eq!(Hex::from_cube(1, -3, 2), Some(Hex::new(1, -3)));
+        assert_eq!(Hex::from_cube(1, 1, 1), None);
+        assert_eq!(Hex::new(1, -3).cube(), (1, -3, 2));
+    }
+
+    #[test]
+    fn stores_sparse_tiles() {
+        let mut map = Hex::ZERO.spiral(1).map(|h| (h, h.q)).collect::<HexMap<_>>();
+        map.set_tile(Hex::new(100, -50), 7);
+
+        assert_eq!(map.len(), 8);
+        assert_eq!(map.neighbours(Hex::new(1, 0)).count(), 3);
+        assert_eq!(map.remove_tile(Hex::new(100, -50)), Some(7));
+        assert_eq!(map.get_tile(Hex::new(-1, 1)), Some(&-1));
+    }
+}
@@ -0,0 +1,208 @@
+use std::str::FromStr;
+
+use crate::{
+    map2d::{ByteMap, CharMap},
+    parse::{parse_lines, Cursor, Parse, ParseError, ParseErrorKind, ParseResult},
+};
+
+/// Block of consecutive non-blank lines from a bigger input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub text: &'a str,
+    pub first_line: usize,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines()
+    }
+
+    pub fn cursor(&self) -> Cursor<'a> {
+        Cursor::at_line(self.text, self.first_line)
+    }
+
+    pub fn parse<T: FromSection>(&self) -> ParseResult<T> {
+        T::from_section(self)
+    }
+
+    fn error(&self, message: impl ToString) -> ParseError {
+        self.cursor().error(ParseErrorKind::Custom(message.to_string()))
+    }
+}
+
+/// Input split on one or more blank lines. Works the same with `\n` and `\r\n` line endings,
+/// and with any amount of leading or trailing blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections<'a> {
+    sections: Vec<Section<'a>>,
+    line_count: usize,
+}
+
+impl<'a> Sections<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let mut sections = Vec::new();
+        let mut current: Option<(usize, usize, usize)> = None;
+        let mut offset = 0;
+        let mut line_count = 0;
+
+        for (i, raw_line) in input.split_inclusive('\n').enumerate() {
+            let line = raw_line.trim_end_matches(['\n', '\r']);
+            let start = offset;
+            offset += raw_line.len();
+            line_count = i + 1;
+
+            if line.trim().is_empty() {
+                if let Some((first_line, from, to)) = current.take() {
+                    sections.push(Section { text: &input[from..to], first_line });
+                }
+            } else {
+                let end = start + line.len();
+                current = Some(current.map_or((i + 1, start, end), |(first_line, from, _)| (first_line, from, end)));
+            }
+        }
+
+        if let Some((first_line, from, to)) = current {
+            sections.push(Section { text: &input[from..to], first_line });
+        }
+
+        Self { sections, line_count }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Section<'a>> {
+        self.sections.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Section<'a>> {
+        self.sections.iter()
+    }
+
+    /// Parses every section at once, usually into a tuple with a type per section.
+    pub fn parse<T: FromSections>(&self) -> ParseResult<T> {
+        T::from_sections(self)
+    }
+
+    fn expect_count(&self, expected: usize) -> ParseResult<()> {
+        if self.len() == expected {
+            return Ok(());
+        }
+
+        let line = self.sections.get(expected).map_or(self.line_count.max(1), |s| s.first_line);
+        Err(ParseError {
+            line,
+            column: 1,
+            kind: ParseErrorKind::Expected(format!("{} sections, found {}", expected, self.len())),
+        })
+    }
+}
+
+pub trait FromSection: Sized {
+    fn from_section(section: &Section) -> ParseResult<Self>;
+}
+
+/// A value per line.
+impl<T: Parse> FromSection for Vec<T> {
+    fn from_section(section: &Section) -> ParseResult<Self> {
+        let lines = parse_lines(section.text, T::parse);
+        lines.map_err(|e| ParseError { line: e.line + section.first_line - 1, ..e })
+    }
+}
+
+impl FromSection for ByteMap {
+    fn from_section(section: &Section) -> ParseResult<Self> {
+        ByteMap::from_str(section.text).map_err(|e| section.error(e))
+    }
+}
+
+impl FromSection for CharMap {
+    fn from_section(section: &Section) -> ParseResult<Self> {
+        CharMap::from_str(section.text).map_err(|e| section.error(e))
+    }
+}
+
+pub trait FromSections: Sized {
+    fn from_sections(sections: &Sections) -> ParseResult<Self>;
+}
+
+/// Any amount of sections of the same type.
+impl<T: FromSection> FromSections for Vec<T> {
+    fn from_sections(sections: &Sections) -> ParseResult<Self> {
+        sections.iter().map(T::from_section).collect()
+    }
+}
+
+macro_rules! impl_from_sections_tuple {
+    ($count:literal: $($t:ident => $i:tt),*) => {
+        impl<$($t: FromSection),*> FromSections for ($($t,)*) {
+            fn from_sections(sections: &Sections) -> ParseResult<Self> {
+                sections.expect_count($count)?;
+                Ok(($($t::from_section(&sections.sections[$i])?,)*))
+            }
+        }
+    };
+}
+
+impl_from_sections_tuple!(1: A => 0);
+impl_from_sections_tuple!(2: A => 0, B => 1);
+impl_from_sections_tuple!(3: A => 0, B => 1, C => 2);
+impl_from_sections_tuple!(4: A => 0, B => 1, C => 2, D => 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::ops::RangeInclusive;
+
+    #[rstest]
+    #[case("3-5\n10-14\n\n1\n5\n")]
+    #[case("3-5\r\n10-14\r\n\r\n1\r\n5\r\n")]
+    #[case("\n3-5\n10-14\n\n\n  \n1\n5\n\n\n")]
+    #[case("3-5\n10-14\n\n1\n5")]
+    fn splits_sections(#[case] input: &str) {
+        let sections = Sections::new(input);
+        assert_eq!(sections.len(), 2);
+
+        let (ranges, ids) = sections.parse::<(Vec<RangeInclusive<u64>>, Vec<u64>)>().unwrap();
+        assert_eq!(ranges, vec![3..=5, 10..=14]);
+        assert_eq!(ids, vec![1, 5]);
+    }
+
+    #[test]
+    fn keeps_line_numbers() {
+        let sections = Sections::new("\n1\n2\n\n\n3\n");
+
+        assert_eq!(sections.get(0).unwrap().first_line, 2);
+        assert_eq!(sections.get(1).unwrap().first_line, 6);
+        assert_eq!(sections.get(1).unwrap().text, "3");
+    }
+
+    #[test]
+    fn reports_errors_at_input_line() {
+        let result = Sections::new("1\n2\n\n3\nx\n").parse::<(Vec<u8>, Vec<u8>)>();
+        assert_eq!(result.unwrap_err().line, 5);
+    }
+
+    #[test]
+    fn reports_wrong_section_count() {
+        let result = Sections::new("1\n\n2\n\n3\n").parse::<(Vec<u8>, Vec<u8>)>();
+        let error = result.unwrap_err();
+
+        assert_eq!(error.line, 5);
+        assert_eq!(error.kind, ParseErrorKind::Expected("2 sections, found 3".to_string()));
+    }
+
+    #[test]
+    fn parses_maps_and_any_section_count() {
+        let maps = Sections::new("ab\ncd\n\nef\ngh\n\nij\nkl\n").parse::<Vec<CharMap>>().unwrap();
+
+        assert_eq!(maps.len(), 3);
+        assert_eq!(maps[2].row(1), &['k', 'l']);
+    }
+}
@@ -1,11 +1,30 @@
+pub mod automaton;
+pub mod beam;
+pub mod bitset;
+pub mod box3d;
+pub mod columns;
+pub mod cycle;
 pub mod direction;
 pub mod direction3d;
+pub mod dot;
+pub mod graph;
+pub mod hex;
+pub mod input;
 pub mod map2d;
+pub mod map3d;
+pub mod memo;
 pub mod point2d;
 pub mod point3d;
+pub mod raster;
+pub mod rect2d;
 pub mod result;
+pub mod rotation3d;
+pub mod sparse;
 pub mod keyed_ord;
+pub mod linalg;
 pub mod num;
+pub mod parse;
+pub mod point;
 
 pub use result::display_result;
 pub use num::*;
@@ -0,0 +1,280 @@
+use crate::bitset::BitSet;
+
+/// Matrix over GF(2), where addition is XOR. Toggle puzzles map to `A x = b` with a column per button,
+/// a row per light and `x` being which buttons get pressed (an odd number of times).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Matrix {
+    rows: Vec<BitSet>,
+    cols: usize,
+}
+
+impl Gf2Matrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self { rows: vec![BitSet::new(cols); rows], cols }
+    }
+
+    /// Builds the matrix from the set rows of each column, like the lights toggled by each button.
+    pub fn from_columns<C>(rows: usize, columns: impl IntoIterator<Item = C>) -> Self
+        where C: IntoIterator<Item = usize>
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| c.into_iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut matrix = Self::new(rows, columns.len());
+        for (col, set_rows) in columns.iter().enumerate() {
+            for &row in set_rows {
+                matrix.toggle(row, col);
+            }
+        }
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row].get(col)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.rows[row].set(col, value);
+    }
+
+    pub fn toggle(&mut self, row: usize, col: usize) {
+        self.rows[row].toggle(col);
+    }
+
+    pub fn multiply(&self, x: &BitSet) -> BitSet {
+        self.rows
+            .iter()
+            .map(|row| (row.clone() & x).count_ones() % 2 == 1)
+            .collect()
+    }
+
+    pub fn rank(&self) -> usize {
+        let mut reduced = self.clone();
+        reduced.reduce(self.cols).len()
+    }
+
+    /// Solves `self * x = target`, returning every solution as a particular one plus the null space.
+    pub fn solve(&self, target: &BitSet) -> Option<Gf2Solution> {
+        assert_eq!(target.len(), self.rows(), "target should have a value per row");
+
+        let mut augmented = Self::new(self.rows(), self.cols + 1);
+        for (r, row) in self.rows.iter().enumerate() {
+            for c in row.iter_ones() {
+                augmented.set(r, c, true);
+            }
+            augmented.set(r, self.cols, target.get(r));
+        }
+
+        let pivots = augmented.reduce(self.cols);
+        let inconsistent = augmented.rows[pivots.len()..]
+            .iter()
+            .any(|row| row.get(self.cols));
+        if inconsistent {
+            return None;
+        }
+
+        let particular = BitSet::from_indices(
+            self.cols,
+            pivots.iter().enumerate().filter(|&(r, _)| augmented.get(r, self.cols)).map(|(_, &p)| p));
+
+        let null_space = (0..self.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut vector = BitSet::new(self.cols);
+                vector.insert(free);
+                for (r, &pivot) in pivots.iter().enumerate() {
+                    if augmented.get(r, free) {
+                        vector.insert(pivot);
+                    }
+                }
+                vector
+            })
+            .collect();
+
+        Some(Gf2Solution { particular, null_space })
+    }
+
+    /// Gauss-Jordan elimination over the first `cols` columns, returning the pivot column of each leading row.
+    fn reduce(&mut self, cols: usize) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        for col in 0..cols {
+            let row = pivots.len();
+            let Some(found) = (row..self.rows()).find(|&r| self.get(r, col)) else {
+                continue;
+            };
+
+            self.rows.swap(row, found);
+            let pivot_row = self.rows[row].clone();
+            for (r, other) in self.rows.iter_mut().enumerate() {
+                if r != row && other.get(col) {
+                    *other ^= &pivot_row;
+                }
+            }
+            pivots.push(col);
+        }
+        pivots
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gf2Solution {
+    particular: BitSet,
+    null_space: Vec<BitSet>,
+}
+
+impl Gf2Solution {
+    pub fn particular(&self) -> &BitSet {
+        &self.particular
+    }
+
+    pub fn null_space(&self) -> &[BitSet] {
+        &self.null_space
+    }
+
+    pub fn free_variables(&self) -> usize {
+        self.null_space.len()
+    }
+
+    /// Every solution of the system, `2^free_variables` of them, in Gray code order.
+    pub fn iter(&self) -> impl Iterator<Item = BitSet> + '_ {
+        let mut counter = BitSet::new(self.null_space.len());
+        let mut next = Some(self.particular.clone());
+        std::iter::from_fn(move || {
+            let current = next.take()?;
+            // Counting up in binary, the bit that becomes 1 is the null space vector to flip
+            if let Some(flip) = (0..counter.len()).find(|&i| !counter.get(i)) {
+                (0..flip).for_each(|i| counter.remove(i));
+                counter.insert(flip);
+                let mut following = current.clone();
+                following ^= &self.null_space[flip];
+                next = Some(following);
+            }
+            Some(current)
+        })
+    }
+
+    /// Solution with the fewest set variables. Each null space vector sets its own free variable,
+    /// so mixing `k` of them gives a solution with at least `k` set variables. Mixes are tried by
+    /// increasing `k` until `k` reaches the best weight found, which stays fast with lots of free
+    /// variables as long as the lightest solution is light.
+    pub fn min_weight(&self) -> Option<BitSet> {
+        let free = self.null_space.len();
+        let mut best = self.particular.clone();
+        for k in 1..=free {
+            let mut picked = (0..k).collect::<Vec<_>>();
+            while k < best.count_ones() {
+                let mut candidate = self.particular.clone();
+                for &i in &picked {
+                    candidate ^= &self.null_space[i];
+                }
+                if candidate.count_ones() < best.count_ones() {
+                    best = candidate;
+                }
+
+                let Some(i) = (0..k).rev().find(|&i| picked[i] < free - k + i) else {
+                    break;
+                };
+                picked[i] += 1;
+                for j in i + 1..k {
+                    picked[j] = picked[j - 1] + 1;
+                }
+            }
+        }
+        Some(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn bits(s: &str) -> BitSet {
+        s.parse().unwrap()
+    }
+
+    fn example_matrix() -> Gf2Matrix {
+        // [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1)
+        Gf2Matrix::from_columns(4, [vec![3], vec![1, 3], vec![2], vec![2, 3], vec![0, 2], vec![0, 1]])
+    }
+
+    #[test]
+    fn every_solution_is_valid() {
+        let matrix = example_matrix();
+        let target = bits(".##.");
+        let solution = matrix.solve(&target).unwrap();
+
+        assert_eq!(solution.free_variables(), matrix.cols() - matrix.rank());
+        assert_eq!(solution.iter().count(), 1 << solution.free_variables());
+        for x in solution.iter() {
+            assert_eq!(matrix.multiply(&x), target);
+        }
+    }
+
+    #[rstest]
+    #[case(".##.", 2)]
+    #[case("....", 0)]
+    #[case("####", 2)]
+    fn finds_min_weight_solution(#[case] target: &str, #[case] expected: usize) {
+        let solution = example_matrix().solve(&bits(target)).unwrap();
+        let best = solution.min_weight().unwrap();
+
+        assert_eq!(best.count_ones(), expected);
+        assert_eq!(example_matrix().multiply(&best), bits(target));
+    }
+
+    #[test]
+    fn many_variables_with_few_free_ones_stay_fast() {
+        // 200 buttons, each toggling its own light and the next one, with 10 spare buttons on top
+        let columns = (0..200).map(|i| vec![i, (i + 1) % 200]).chain((0..10).map(|i| vec![i * 20]));
+        let matrix = Gf2Matrix::from_columns(200, columns);
+        let target = BitSet::from_indices(200, [0, 1]);
+
+        let solution = matrix.solve(&target).unwrap();
+        assert_eq!(solution.free_variables(), 10);
+        assert_eq!(solution.min_weight().unwrap().count_ones(), 1);
+    }
+
+    #[test]
+    fn finds_light_solutions_among_many_free_variables() {
+        // A chain of 99 buttons toggling neighbours, plus a button per light
+        let columns = (0..99).map(|i| vec![i, i + 1]).chain((0..100).map(|i| vec![i]));
+        let matrix = Gf2Matrix::from_columns(100, columns);
+        let target = BitSet::from_indices(100, [0, 99]);
+
+        let solution = matrix.solve(&target).unwrap();
+        assert_eq!(solution.free_variables(), 99);
+        let best = solution.min_weight().unwrap();
+        assert_eq!(best.count_ones(), 2);
+        assert_eq!(matrix.multiply(&best), target);
+        assert!(solution.iter().take(100).all(|v| matrix.multiply(&v) == target));
+    }
+
+    #[test]
+    fn returns_none_when_inconsistent() {
+        let matrix = Gf2Matrix::from_columns(3, [vec![0, 1], vec![1, 2]]);
+        assert_eq!(matrix.solve(&bits("#..")), None);
+    }
+
+    #[test]
+    fn handles_more_than_a_word_of_columns() {
+        let matrix = Gf2Matrix::from_columns(100, (0..100).map(|i| vec![i, (i + 1) % 100]));
+        let target = BitSet::from_indices(100, [0, 70]);
+
+        let solution = matrix.solve(&target).unwrap();
+        assert_eq!(solution.free_variables(), 1);
+        let best = solution.min_weight().unwrap();
+        assert_eq!(best.count_ones(), 30);
+        assert_eq!(matrix.multiply(&best), target);
+    }
+}
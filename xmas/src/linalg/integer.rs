@@ -0,0 +1,345 @@
+use super::rational::{row_reduce, Rational};
+use crate::num::lcm_all;
+
+/// System `A x = b` where only non-negative integer values of `x` are accepted, like counting button presses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegerSystem {
+    coefficients: Vec<Vec<i64>>,
+    target: Vec<i64>,
+}
+
+impl IntegerSystem {
+    pub fn new(coefficients: Vec<Vec<i64>>, target: Vec<i64>) -> Self {
+        assert_eq!(coefficients.len(), target.len(), "target should have a value per row");
+        let variables = coefficients.first().map_or(0, |r| r.len());
+        assert!(coefficients.iter().all(|r| r.len() == variables), "all rows should have the same length");
+        Self { coefficients, target }
+    }
+
+    /// Builds the system from the rows each variable adds one to, like the counters increased by each button.
+    pub fn from_columns<C>(columns: impl IntoIterator<Item = C>, target: Vec<i64>) -> Self
+        where C: IntoIterator<Item = usize>
+    {
+        let columns = columns
+            .into_iter()
+            .map(|c| c.into_iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut coefficients = vec![vec![0; columns.len()]; target.len()];
+        for (col, rows) in columns.iter().enumerate() {
+            for &row in rows {
+                coefficients[row][col] += 1;
+            }
+        }
+        Self::new(coefficients, target)
+    }
+
+    pub fn variables(&self) -> usize {
+        self.coefficients.first().map_or(0, |r| r.len())
+    }
+
+    pub fn is_solution(&self, x: &[i64]) -> bool {
+        x.len() == self.variables() && self.coefficients
+            .iter()
+            .zip(&self.target)
+            .all(|(row, &b)| row.iter().zip(x).map(|(a, x)| a * x).sum::<i64>() == b)
+    }
+
+    /// Each variable can't go over the smallest target it contributes to.
+    /// Only possible to know when no coefficient or target is negative.
+    pub fn infer_upper_bounds(&self) -> Option<Vec<i64>> {
+        let any_negative = self.target.iter().any(|&b| b < 0)
+            || self.coefficients.iter().flatten().any(|&a| a < 0);
+        if any_negative {
+            return None;
+        }
+
+        let bounds = (0..self.variables())
+            .map(|col| self.coefficients
+                .iter()
+                .zip(&self.target)
+                .filter(|(row, _)| row[col] > 0)
+                .map(|(row, &b)| b / row[col])
+                .min()
+                .unwrap_or(0))
+            .collect();
+        Some(bounds)
+    }
+
+    /// Reduces the system with exact fractions. Returns `None` if it has no solution, even a fractional one.
+    pub fn reduce(&self) -> Option<ReducedSystem> {
+        let variables = self.variables();
+        let mut matrix = self.coefficients
+            .iter()
+            .zip(&self.target)
+            .map(|(row, &b)| row.iter().chain([&b]).map(|&v| Rational::from(v)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let pivots = row_reduce(&mut matrix, variables);
+        if matrix[pivots.len()..].iter().any(|row| !row[variables].is_zero()) {
+            return None;
+        }
+
+        let free = (0..variables)
+            .filter(|c| !pivots.contains(c))
+            .collect::<Vec<_>>();
+
+        // Scaling every row by its common denominator keeps the search in integers:
+        // scale * x_pivot = rhs - sum(free_coefficients * x_free)
+        let rows = pivots
+            .iter()
+            .enumerate()
+            .map(|(r, &pivot)| {
+                let row = &matrix[r];
+                let scale = lcm_all(free
+                    .iter()
+                    .map(|&f| row[f].denominator())
+                    .chain([row[variables].denominator()]));
+                let scaled = |value: Rational| (value * Rational::new(scale, 1)).to_integer().unwrap();
+                ReducedRow {
+                    pivot,
+                    scale,
+                    rhs: scaled(row[variables]),
+                    free_coefficients: free.iter().map(|&f| scaled(row[f])).collect(),
+                }
+            })
+            .collect();
+
+        Some(ReducedSystem { variables, free, rows })
+    }
+
+    /// Finds the solution with the smallest sum, using [`IntegerSystem::infer_upper_bounds`].
+    /// Returns `None` when there is no solution or when bounds can't be inferred because of negative
+    /// values, in which case [`IntegerSystem::minimize_sum_bounded`] has to be used.
+    pub fn minimize_sum(&self) -> Option<Vec<i64>> {
+        let bounds = self.infer_upper_bounds()?;
+        self.minimize_sum_bounded(&bounds)
+    }
+
+    /// Finds the solution with the smallest sum where every `x[i]` is in `0..=upper_bounds[i]`,
+    /// branching over the values of the free variables.
+    pub fn minimize_sum_bounded(&self, upper_bounds: &[i64]) -> Option<Vec<i64>> {
+        assert_eq!(upper_bounds.len(), self.variables());
+        let reduced = self.reduce()?;
+
+        let mut search = Search::new(&reduced, upper_bounds);
+        search.run(0);
+        search.best.map(|(_, solution)| solution)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReducedSystem {
+    variables: usize,
+    free: Vec<usize>,
+    rows: Vec<ReducedRow>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReducedRow {
+    pivot: usize,
+    scale: i128,
+    rhs: i128,
+    free_coefficients: Vec<i128>,
+}
+
+impl ReducedSystem {
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free
+    }
+
+    pub fn pivot_variables(&self) -> impl Iterator<Item = usize> + '_ {
+        self.rows.iter().map(|r| r.pivot)
+    }
+
+    /// Fills in the pivot variables from the free ones, if they all come out as integers.
+    pub fn solve_with(&self, free_values: &[i64]) -> Option<Vec<i64>> {
+        assert_eq!(free_values.len(), self.free.len());
+        let mut solution = vec![0; self.variables];
+        for (&f, &value) in self.free.iter().zip(free_values) {
+            solution[f] = value;
+        }
+
+        for row in &self.rows {
+            let value = row.rhs - row.free_coefficients
+                .iter()
+                .zip(free_values)
+                .map(|(&c, &x)| c * x as i128)
+                .sum::<i128>();
+            if value % row.scale != 0 {
+                return None;
+            }
+            solution[row.pivot] = i64::try_from(value / row.scale).ok()?;
+        }
+        Some(solution)
+    }
+}
+
+/// Branch and bound over the free variables. Both the pivot variables and the total are linear in them,
+/// so the values the unassigned ones could still take give exact ranges to prune with.
+struct Search<'a> {
+    reduced: &'a ReducedSystem,
+    upper_bounds: &'a [i64],
+    free_values: Vec<i64>,
+    /// `scale * x_pivot` of each row, from the free variables assigned so far
+    partial_rows: Vec<i128>,
+    /// Range the unassigned free variables starting at each index can add to each row
+    rest_row_ranges: Vec<Vec<(i128, i128)>>,
+    /// The total times `objective_scale` is a constant plus these per free variable
+    objective_coefficients: Vec<i128>,
+    objective_scale: i128,
+    partial_objective: i128,
+    rest_objective_min: Vec<i128>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl<'a> Search<'a> {
+    fn new(reduced: &'a ReducedSystem, upper_bounds: &'a [i64]) -> Self {
+        let free_count = reduced.free.len();
+        let free_bound = |i: usize| upper_bounds[reduced.free[i]] as i128;
+
+        let rest_row_ranges = reduced.rows
+            .iter()
+            .map(|row| {
+                let mut ranges = vec![(0, 0); free_count + 1];
+                for i in (0..free_count).rev() {
+                    let contribution = -row.free_coefficients[i] * free_bound(i);
+                    let (min, max) = ranges[i + 1];
+                    ranges[i] = (min + contribution.min(0), max + contribution.max(0));
+                }
+                ranges
+            })
+            .collect();
+
+        let objective_scale = lcm_all(reduced.rows.iter().map(|r| r.scale));
+        let objective_base = reduced.rows
+            .iter()
+            .map(|r| r.rhs * (objective_scale / r.scale))
+            .sum();
+        let objective_coefficients = (0..free_count)
+            .map(|i| objective_scale - reduced.rows
+                .iter()
+                .map(|r| r.free_coefficients[i] * (objective_scale / r.scale))
+                .sum::<i128>())
+            .collect::<Vec<_>>();
+
+        let mut rest_objective_min = vec![0; free_count + 1];
+        for i in (0..free_count).rev() {
+            rest_objective_min[i] = rest_objective_min[i + 1] + (objective_coefficients[i] * free_bound(i)).min(0);
+        }
+
+        Self {
+            reduced,
+            upper_bounds,
+            free_values: vec![0; free_count],
+            partial_rows: reduced.rows.iter().map(|r| r.rhs).collect(),
+            rest_row_ranges,
+            objective_coefficients,
+            objective_scale,
+            partial_objective: objective_base,
+            rest_objective_min,
+            best: None,
+        }
+    }
+
+    fn run(&mut self, index: usize) {
+        let lowest_objective = self.partial_objective + self.rest_objective_min[index];
+        if self.best.as_ref().is_some_and(|(best, _)| lowest_objective >= *best as i128 * self.objective_scale) {
+            return;
+        }
+
+        let rows_reachable = self.reduced.rows
+            .iter()
+            .enumerate()
+            .all(|(r, row)| {
+                let (min, max) = self.rest_row_ranges[r][index];
+                let highest = row.scale * self.upper_bounds[row.pivot] as i128;
+                self.partial_rows[r] + max >= 0 && self.partial_rows[r] + min <= highest
+            });
+        if !rows_reachable {
+            return;
+        }
+
+        if index == self.free_values.len() {
+            let Some(solution) = self.reduced.solve_with(&self.free_values) else {
+                return;
+            };
+
+            let in_bounds = solution.iter().zip(self.upper_bounds).all(|(&x, &max)| (0..=max).contains(&x));
+            let sum = solution.iter().sum::<i64>();
+            if in_bounds && self.best.as_ref().is_none_or(|(best, _)| sum < *best) {
+                self.best = Some((sum, solution));
+            }
+            return;
+        }
+
+        for value in 0..=self.upper_bounds[self.reduced.free[index]] {
+            self.assign(index, value);
+            self.run(index + 1);
+        }
+        self.assign(index, 0);
+    }
+
+    fn assign(&mut self, index: usize, value: i64) {
+        let delta = (value - self.free_values[index]) as i128;
+        self.free_values[index] = value;
+        for (partial, row) in self.partial_rows.iter_mut().zip(&self.reduced.rows) {
+            *partial -= row.free_coefficients[index] * delta;
+        }
+        self.partial_objective += self.objective_coefficients[index] * delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    // (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+    #[case(vec![vec![3], vec![1, 3], vec![2], vec![2, 3], vec![0, 2], vec![0, 1]], vec![3, 5, 4, 7], Some(10))]
+    // (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+    #[case(vec![vec![0, 2, 3, 4], vec![2, 3], vec![0, 4], vec![0, 1, 2], vec![1, 2, 3, 4]], vec![7, 5, 12, 7, 2], Some(12))]
+    // (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+    #[case(vec![vec![0, 1, 2, 3, 4], vec![0, 3, 4], vec![0, 1, 2, 4, 5], vec![1, 2]], vec![10, 11, 11, 5, 10, 5], Some(11))]
+    #[case(vec![vec![0, 1]], vec![1, 2], None)]
+    #[case(vec![vec![0, 1], vec![1]], vec![1, 0], None)]
+    fn minimizes_button_presses(
+        #[case] buttons: Vec<Vec<usize>>,
+        #[case] target: Vec<i64>,
+        #[case] expected: Option<i64>,
+    ) {
+        let system = IntegerSystem::from_columns(buttons, target);
+        let solution = system.minimize_sum();
+
+        assert_eq!(solution.as_ref().map(|s| s.iter().sum()), expected);
+        assert!(solution.is_none_or(|s| system.is_solution(&s)));
+    }
+
+    #[test]
+    fn rejects_fractional_solutions() {
+        let system = IntegerSystem::new(vec![vec![2]], vec![3]);
+
+        assert!(system.reduce().is_some());
+        assert_eq!(system.minimize_sum(), None);
+    }
+
+    #[test]
+    fn reports_free_variables() {
+        let system = IntegerSystem::from_columns([vec![0], vec![0, 1], vec![1]], vec![2, 2]);
+        let reduced = system.reduce().unwrap();
+
+        assert_eq!(reduced.pivot_variables().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(reduced.free_variables(), &[2]);
+        assert_eq!(reduced.solve_with(&[1]), Some(vec![1, 1, 1]));
+    }
+
+    #[test]
+    fn respects_explicit_bounds() {
+        let system = IntegerSystem::new(vec![vec![1, -1]], vec![2]);
+
+        assert_eq!(system.infer_upper_bounds(), None);
+        assert_eq!(system.minimize_sum(), None);
+        assert_eq!(system.minimize_sum_bounded(&[5, 5]), Some(vec![2, 0]));
+    }
+}
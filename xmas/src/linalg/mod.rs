@@ -0,0 +1,3 @@
+pub mod gf2;
+pub mod integer;
+pub mod rational;
@@ -0,0 +1,174 @@
+use std::{cmp::Ordering, fmt::Display, ops};
+
+use crate::num::gcd;
+
+/// Exact fraction, always stored reduced and with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "denominator can't be zero");
+        let divisor = gcd(num, den) * den.signum();
+        Self { num: num / divisor, den: den / divisor }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self { num: value as i128, den: 1 }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl ops::Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.num * rhs.den + rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl ops::Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl ops::Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl ops::Div for Rational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(self.num * rhs.den, self.den * rhs.num)
+    }
+}
+
+impl ops::Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self { num: -self.num, den: self.den }
+    }
+}
+
+/// Gauss-Jordan elimination over the first `cols` columns, leaving every pivot as 1.
+/// Returns the pivot column of each leading row, the rest of the rows being zero in those columns.
+pub fn row_reduce(matrix: &mut [Vec<Rational>], cols: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    for col in 0..cols {
+        let row = pivots.len();
+        let Some(found) = (row..matrix.len()).find(|&r| !matrix[r][col].is_zero()) else {
+            continue;
+        };
+
+        matrix.swap(row, found);
+        let pivot = matrix[row][col];
+        for value in matrix[row].iter_mut() {
+            *value = *value / pivot;
+        }
+
+        let pivot_row = matrix[row].clone();
+        for (r, other) in matrix.iter_mut().enumerate() {
+            let factor = other[col];
+            if r == row || factor.is_zero() {
+                continue;
+            }
+
+            for (value, &pivot_value) in other.iter_mut().zip(&pivot_row) {
+                *value = *value - factor * pivot_value;
+            }
+        }
+        pivots.push(col);
+    }
+    pivots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Rational::new(2, 4), Rational::new(1, 2))]
+    #[case(Rational::new(3, -6), Rational::new(-1, 2))]
+    #[case(Rational::new(0, -5), Rational::ZERO)]
+    #[case(Rational::new(1, 3) + Rational::new(1, 6), Rational::new(1, 2))]
+    #[case(Rational::new(1, 3) - Rational::new(1, 2), Rational::new(-1, 6))]
+    #[case(Rational::new(2, 3) * Rational::new(3, 4), Rational::new(1, 2))]
+    #[case(Rational::new(2, 3) / Rational::new(-4, 3), Rational::new(-1, 2))]
+    fn normalizes_results(#[case] result: Rational, #[case] expected: Rational) {
+        assert_eq!(result, expected);
+        assert!(result.denominator() > 0);
+    }
+
+    #[test]
+    fn reduces_rows() {
+        let mut matrix = [
+            [2, 1, -1, 8],
+            [-3, -1, 2, -11],
+            [-2, 1, 2, -3],
+        ].map(|row| row.map(Rational::from).to_vec());
+
+        let pivots = row_reduce(&mut matrix, 3);
+        assert_eq!(pivots, vec![0, 1, 2]);
+        let solution = matrix.iter().map(|row| row[3]).collect::<Vec<_>>();
+        assert_eq!(solution, [2, 3, -1].map(Rational::from));
+    }
+}
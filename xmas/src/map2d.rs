@@ -2,12 +2,12 @@ use core::str;
 use std::{convert::Infallible, fmt::Display, str::FromStr};
 use thiserror::Error;
 
-use crate::point2d::Point2D;
+use crate::{direction::QuarterRotation, point2d::Point2D, rect2d::Rect2D};
 
 pub type ByteMap = Map2D<u8>;
 pub type CharMap = Map2D<char>;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Map2D<Tile = u8> {
     map: Vec<Tile>,
     width: usize,
@@ -27,6 +27,121 @@ impl<T: Clone> Map2D<T> {
     }
 }
 
+/// How to load text where some lines are shorter than others, like whitespace-aligned inputs
+/// after an editor stripped trailing spaces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaddingOptions<T> {
+    tile: T,
+    trim_trailing_empty_lines: bool,
+}
+
+impl<T> PaddingOptions<T> {
+    pub fn new(tile: T) -> Self {
+        Self { tile, trim_trailing_empty_lines: false }
+    }
+
+    /// Drops blank lines at the end instead of turning them into rows of padding.
+    pub fn trim_trailing_empty_lines(mut self, trim: bool) -> Self {
+        self.trim_trailing_empty_lines = trim;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaddedMap<T> {
+    pub map: Map2D<T>,
+    /// Rows that were shorter than the widest one, 0-based.
+    pub padded_lines: Vec<usize>,
+}
+
+impl<T: Clone> Map2D<T> {
+    /// Like [`Map2D::from_str_with_parser`], but short rows are padded up to the widest one.
+    pub fn from_str_padded<'a, Iter, Parser>(
+        s: &'a str,
+        parser: &mut Parser,
+        options: &PaddingOptions<T>,
+    ) -> Result<PaddedMap<T>, ParseMapError>
+        where Iter: Iterator<Item = T>,
+        Parser: FnMut(&'a str) -> Iter
+    {
+        let mut lines = s.lines().collect::<Vec<_>>();
+        if options.trim_trailing_empty_lines {
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+        }
+
+        if lines.is_empty() {
+            return Err(ParseMapError::EmptyString);
+        }
+
+        let rows = lines.into_iter().map(|l| parser(l).collect::<Vec<_>>()).collect::<Vec<_>>();
+        let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+
+        let mut map = Self { map: Vec::with_capacity(width * rows.len()), width, height: 0 };
+        let mut padded_lines = Vec::new();
+        for (y, mut row) in rows.into_iter().enumerate() {
+            if row.len() < width {
+                padded_lines.push(y);
+                row.resize(width, options.tile.clone());
+            }
+            map.add_row(row.into_iter())?;
+        }
+
+        Ok(PaddedMap { map, padded_lines })
+    }
+}
+
+impl<T: Clone> Map2D<T> {
+    /// Swaps rows and columns, so column-wise puzzles can reuse row-based code.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(Point2D(self.height as isize, self.width as isize), |p| self.tile(Point2D(p.1, p.0)))
+    }
+
+    /// Rotates the whole map, `QuarterRotation::Right` being clockwise as seen on screen.
+    pub fn rotate(&self, rotation: QuarterRotation) -> Self {
+        let (w, h) = (self.width as isize, self.height as isize);
+        match rotation {
+            QuarterRotation::None => self.clone(),
+            QuarterRotation::Right => Self::from_fn(Point2D(h, w), |p| self.tile(Point2D(p.1, h - 1 - p.0))),
+            QuarterRotation::TurnAround => Self::from_fn(self.size(), |p| self.tile(Point2D(w - 1 - p.0, h - 1 - p.1))),
+            QuarterRotation::Left => Self::from_fn(Point2D(h, w), |p| self.tile(Point2D(w - 1 - p.1, p.0))),
+        }
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.size(), |p| self.tile(Point2D(self.width as isize - 1 - p.0, p.1)))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.size(), |p| self.tile(Point2D(p.0, self.height as isize - 1 - p.1)))
+    }
+
+    /// Part of the map inside `rect`, clipped to the map. Empty if they don't overlap.
+    pub fn crop(&self, rect: Rect2D) -> Self {
+        match self.bounds().and_then(|b| b.intersection(&rect)) {
+            Some(rect) => Self::from_fn(rect.size(), |p| self.tile(rect.min() + p)),
+            None => Self::from_fn(Point2D::ZERO, |p| self.tile(p)),
+        }
+    }
+
+    /// Surrounds the map with `n` tiles on every side.
+    pub fn pad(&self, n: usize, tile: T) -> Self {
+        let offset = Point2D(n as isize, n as isize);
+        let mut map = Self::new_filled(self.size() + offset.scale(Point2D(2, 2)), tile);
+        for (point, t) in self.iter_with_points() {
+            map.set_tile(point + offset, t.clone());
+        }
+        map
+    }
+
+    fn tile(&self, point: Point2D) -> T {
+        self.get_tile(point).expect("point should be inside the map").clone()
+    }
+}
+
 impl<T: Default> Map2D<T> {
     pub fn new_with_default_tiles(size: Point2D) -> Self {
         let width = size.0 as usize;
@@ -44,6 +159,22 @@ impl<T: Default> Map2D<T> {
 }
 
 impl<T> Map2D<T> {
+    /// Negative sizes are treated as 0.
+    pub fn from_fn(size: Point2D, mut f: impl FnMut(Point2D) -> T) -> Self {
+        let size = size.max(Point2D::ZERO);
+        let width = size.0 as usize;
+        let height = size.1 as usize;
+        let map = (0..size.1)
+            .flat_map(|y| (0..size.0).map(move |x| Point2D(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            map,
+            width,
+            height,
+        }
+    }
+
     pub fn from_str_with_parser<'a, Iter, Parser>(
         s: &'a str,
         parser: &mut Parser,
@@ -87,7 +218,7 @@ impl<T> Map2D<T> {
         } else if tiles.len() != self.width {
             return Err(ParseMapError::InconsistentRowSize { current: tiles.len(), expected: self.width });
         }
-        self.map.extend(tiles.into_iter());
+        self.map.extend(tiles);
         self.height += 1;
         Ok(())
     }
@@ -142,6 +273,16 @@ impl<T> Map2D<T> {
         (0..self.height).map(|y| self.row(y))
     }
 
+    /// Tiles of a column, top to bottom.
+    pub fn column(&self, index: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(index < self.width, "column {index} out of bounds for a width of {}", self.width);
+        self.map.iter().skip(index).step_by(self.width)
+    }
+
+    pub fn columns_iter(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
     pub fn width(&self) -> usize {
         self.width
     }
@@ -153,6 +294,11 @@ impl<T> Map2D<T> {
     pub fn size(&self) -> Point2D {
         Point2D(self.width as isize, self.height as isize)
     }
+
+    /// Every point inside the map, or `None` for an empty one.
+    pub fn bounds(&self) -> Option<Rect2D> {
+        Rect2D::from_size(Point2D::ZERO, self.size())
+    }
 }
 
 impl<T: PartialEq> Map2D<T> {
@@ -163,7 +309,7 @@ impl<T: PartialEq> Map2D<T> {
     }
 }
 
-#[derive(Debug, Error, PartialEq, Eq)]
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
 pub enum ParseMapError<TileErr = Infallible> {
     #[error("Can't parse an empty string to a Map2D")]
     EmptyString,
@@ -181,6 +327,12 @@ impl FromStr for Map2D<u8> {
     }
 }
 
+impl Map2D<u8> {
+    pub fn parse_padded(s: &str, options: &PaddingOptions<u8>) -> Result<PaddedMap<u8>, ParseMapError> {
+        Self::from_str_padded(s, &mut str::bytes, options)
+    }
+}
+
 impl Display for Map2D<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for line in self.rows_iter() {
@@ -198,6 +350,12 @@ impl FromStr for Map2D<char> {
     }
 }
 
+impl Map2D<char> {
+    pub fn parse_padded(s: &str, options: &PaddingOptions<char>) -> Result<PaddedMap<char>, ParseMapError> {
+        Self::from_str_padded(s, &mut str::chars, options)
+    }
+}
+
 impl Display for Map2D<char> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for line in self.rows_iter() {
@@ -252,6 +410,14 @@ mod tests {
         assert_eq!(map.height, 3);
     }
 
+    #[test]
+    fn bounds_match_size() {
+        let bounds = ByteMap::new_with_default_tiles(Point2D(20, 10)).bounds().unwrap();
+
+        assert_eq!((bounds.min(), bounds.max()), (Point2D(0, 0), Point2D(19, 9)));
+        assert_eq!(ByteMap::new_with_default_tiles(Point2D(0, 10)).bounds(), None);
+    }
+
     #[test]
     fn parse_map_returns_empty_error() {
         let result = ByteMap::from_str("");
@@ -269,4 +435,93 @@ mod tests {
         let result = ByteMap::from_str(MAP);
         assert_eq!(result, Err(ParseMapError::InconsistentRowSize { current: 3, expected: 4 }))
     }
+
+    #[rstest]
+    #[case(false, 4, vec![1, 3])]
+    #[case(true, 3, vec![1])]
+    fn parse_padded_fills_short_rows(
+        #[case] trim: bool,
+        #[case] height: usize,
+        #[case] padded: Vec<usize>,
+    ) {
+        const MAP: &str = concat!(
+            "0123\n",
+            "45\n",
+            "89AB\n",
+            "\n",
+        );
+
+        let options = PaddingOptions::new(b'.').trim_trailing_empty_lines(trim);
+        let result = ByteMap::parse_padded(MAP, &options).unwrap();
+
+        assert_eq!(result.map.height(), height);
+        assert_eq!(result.map.row(1), b"45..");
+        assert_eq!(result.padded_lines, padded);
+    }
+
+    #[test]
+    fn parse_padded_returns_empty_error() {
+        let options = PaddingOptions::new(' ').trim_trailing_empty_lines(true);
+        assert_eq!(CharMap::parse_padded(" \n\n", &options), Err(ParseMapError::EmptyString));
+    }
+
+    const SMALL: &str = concat!(
+        "abc\n",
+        "def\n",
+    );
+
+    #[rstest]
+    #[case(QuarterRotation::None, "abc\ndef\n")]
+    #[case(QuarterRotation::Right, "da\neb\nfc\n")]
+    #[case(QuarterRotation::TurnAround, "fed\ncba\n")]
+    #[case(QuarterRotation::Left, "cf\nbe\nad\n")]
+    fn rotates_map(#[case] rotation: QuarterRotation, #[case] expected: &str) {
+        let map = ByteMap::from_str(SMALL).unwrap();
+        assert_eq!(map.rotate(rotation).to_string(), expected);
+    }
+
+    #[test]
+    fn rotations_compose() {
+        let map = ByteMap::from_str(SMALL).unwrap();
+
+        assert_eq!(map.rotate(QuarterRotation::Right).rotate(QuarterRotation::Left), map);
+        assert_eq!(map.rotate(QuarterRotation::Right).rotate(QuarterRotation::Right), map.rotate(QuarterRotation::TurnAround));
+        assert_eq!(map.flip_horizontal().flip_vertical(), map.rotate(QuarterRotation::TurnAround));
+    }
+
+    #[test]
+    fn transposes_and_flips() {
+        let map = ByteMap::from_str(SMALL).unwrap();
+
+        assert_eq!(map.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(map.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(map.flip_vertical().to_string(), "def\nabc\n");
+    }
+
+    #[rstest]
+    #[case(Rect2D::from_corners(Point2D(1, 0), Point2D(2, 1)), "bc\nef\n")]
+    #[case(Rect2D::from_corners(Point2D(2, 1), Point2D(6, 5)), "f\n")]
+    #[case(Rect2D::from_corners(Point2D(-1, -1), Point2D(0, 0)), "a\n")]
+    #[case(Rect2D::from_corners(Point2D(3, 0), Point2D(3, 0)), "")]
+    fn crops_map(#[case] rect: Rect2D, #[case] expected: &str) {
+        let map = ByteMap::from_str(SMALL).unwrap();
+        assert_eq!(map.crop(rect).to_string(), expected);
+    }
+
+    #[test]
+    fn from_fn_clamps_negative_sizes() {
+        let map = ByteMap::from_fn(Point2D(-3, 2), |_| b'#');
+
+        assert_eq!(map.size(), Point2D(0, 2));
+        assert_eq!(map.bounds(), None);
+    }
+
+    #[test]
+    fn pads_and_reads_columns() {
+        let map = ByteMap::from_str(SMALL).unwrap().pad(1, b'.');
+
+        assert_eq!(map.to_string(), ".....\n.abc.\n.def.\n.....\n");
+        assert_eq!(map.column(2).copied().collect::<Vec<_>>(), b".be.");
+        assert_eq!(map.columns_iter().count(), 5);
+    }
 }
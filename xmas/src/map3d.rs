@@ -0,0 +1,337 @@
+use std::{collections::VecDeque, str::FromStr};
+use thiserror::Error;
+
+use crate::{
+    box3d::Box3D,
+    direction3d::DIRECTIONS_3D,
+    input::Sections,
+    map2d::{ByteMap, ParseMapError},
+    point::ParsePointError,
+    point3d::Point3D,
+};
+
+/// Dense 3D grid, stored layer by layer along z.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map3D<Tile = u8> {
+    map: Vec<Tile>,
+    size: Point3D,
+}
+
+impl<T: Clone> Map3D<T> {
+    pub fn new_filled(size: Point3D, tile: T) -> Self {
+        let size = size.max(Point3D::ZERO);
+        Self { map: vec![tile; (size.0 * size.1 * size.2) as usize], size }
+    }
+}
+
+impl<T> Map3D<T> {
+    pub fn from_fn(size: Point3D, f: impl FnMut(Point3D) -> T) -> Self {
+        let size = size.max(Point3D::ZERO);
+        let map = Self::points_in(size).map(f).collect();
+        Self { map, size }
+    }
+
+    pub fn is_inside(&self, point: Point3D) -> bool {
+        point.max(Point3D::ZERO) == point && point.min(self.size - Point3D(1, 1, 1)) == point
+    }
+
+    pub fn get_index(&self, point: Point3D) -> Option<usize> {
+        self.is_inside(point)
+            .then(|| (point.0 + self.size.0 * (point.1 + self.size.1 * point.2)) as usize)
+    }
+
+    pub fn get_tile(&self, point: Point3D) -> Option<&T> {
+        self.get_index(point).map(|i| &self.map[i])
+    }
+
+    pub fn get_tile_mut(&mut self, point: Point3D) -> Option<&mut T> {
+        self.get_index(point).map(|i| &mut self.map[i])
+    }
+
+    pub fn set_tile(&mut self, point: Point3D, tile: T) -> bool {
+        if let Some(index) = self.get_index(point) {
+            self.map[index] = tile;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn iter_points(&self) -> impl Iterator<Item = Point3D> + '_ {
+        Self::points_in(self.size)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.map.iter()
+    }
+
+    pub fn iter_with_points(&self) -> impl Iterator<Item = (Point3D, &T)> + '_ {
+        self.iter_points().zip(self.map.iter())
+    }
+
+    pub fn size(&self) -> Point3D {
+        self.size
+    }
+
+    pub fn width(&self) -> usize {
+        self.size.0 as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.size.1 as usize
+    }
+
+    pub fn depth(&self) -> usize {
+        self.size.2 as usize
+    }
+
+    /// Every point inside the map, or `None` for an empty one.
+    pub fn bounds(&self) -> Option<Box3D> {
+        (!self.map.is_empty()).then(|| Box3D::from_corners(Point3D::ZERO, self.size - Point3D(1, 1, 1)))
+    }
+
+    /// Face neighbours inside the map.
+    pub fn neighbours_6(&self, point: Point3D) -> impl Iterator<Item = Point3D> + '_ {
+        DIRECTIONS_3D
+            .iter()
+            .map(move |d| point + d.as_point())
+            .filter(|&p| self.is_inside(p))
+    }
+
+    /// Face, edge and corner neighbours inside the map.
+    pub fn neighbours_26(&self, point: Point3D) -> impl Iterator<Item = Point3D> + '_ {
+        Self::points_in(Point3D(3, 3, 3))
+            .map(|offset| offset - Point3D(1, 1, 1))
+            .filter(|&offset| offset != Point3D::ZERO)
+            .map(move |offset| point + offset)
+            .filter(|&p| self.is_inside(p))
+    }
+
+    /// Points reachable from `start` through face neighbours that `can_enter` accepts.
+    pub fn flood_fill(&self, start: Point3D, can_enter: impl Fn(&T) -> bool) -> Vec<Point3D> {
+        let mut visited = vec![false; self.map.len()];
+        self.fill_from([start], &can_enter, &mut visited)
+    }
+
+    /// Faces of solid tiles that touch a non-solid tile or the outside of the map.
+    pub fn surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        self.iter_with_points()
+            .filter(|(_, t)| is_solid(t))
+            .map(|(p, _)| {
+                DIRECTIONS_3D
+                    .iter()
+                    .filter(|d| self.get_tile(p + d.as_point()).is_none_or(|t| !is_solid(t)))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Like [`Map3D::surface_area`], but without the faces inside enclosed pockets.
+    pub fn exterior_surface_area(&self, is_solid: impl Fn(&T) -> bool) -> usize {
+        let outside = self.outside(&is_solid);
+        self.iter_with_points()
+            .filter(|(_, t)| is_solid(t))
+            .map(|(p, _)| {
+                DIRECTIONS_3D
+                    .iter()
+                    .map(|d| p + d.as_point())
+                    .filter(|&n| self.get_index(n).is_none_or(|i| outside[i]))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Groups of non-solid tiles that can't reach the outside of the map.
+    pub fn enclosed_pockets(&self, is_solid: impl Fn(&T) -> bool) -> Vec<Vec<Point3D>> {
+        let mut visited = self.outside(&is_solid);
+        let mut pockets = Vec::new();
+        for (i, point) in self.iter_points().enumerate() {
+            if !visited[i] && !is_solid(&self.map[i]) {
+                pockets.push(self.fill_from([point], |t| !is_solid(t), &mut visited));
+            }
+        }
+        pockets
+    }
+
+    /// Which tiles are non-solid and connected to the outside of the map.
+    fn outside(&self, is_solid: impl Fn(&T) -> bool) -> Vec<bool> {
+        let mut visited = vec![false; self.map.len()];
+        let edge = self.bounds().into_iter().flat_map(|b| b.iter_surface().collect::<Vec<_>>());
+        self.fill_from(edge, |t| !is_solid(t), &mut visited);
+        visited
+    }
+
+    /// Breadth first fill from every start at once, skipping and marking tiles in `visited`.
+    fn fill_from(
+        &self,
+        starts: impl IntoIterator<Item = Point3D>,
+        can_enter: impl Fn(&T) -> bool,
+        visited: &mut [bool],
+    ) -> Vec<Point3D> {
+        let mut queue = VecDeque::new();
+        for start in starts {
+            if let Some(i) = self.get_index(start).filter(|&i| !visited[i] && can_enter(&self.map[i])) {
+                visited[i] = true;
+                queue.push_back(start);
+            }
+        }
+
+        let mut reached = Vec::new();
+        while let Some(point) = queue.pop_front() {
+            reached.push(point);
+            for next in self.neighbours_6(point) {
+                let i = self.get_index(next).unwrap();
+                if !visited[i] && can_enter(&self.map[i]) {
+                    visited[i] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached
+    }
+
+    fn points_in(size: Point3D) -> impl Iterator<Item = Point3D> {
+        (0..size.2).flat_map(move |z| {
+            (0..size.1).flat_map(move |y| (0..size.0).map(move |x| Point3D(x, y, z)))
+        })
+    }
+}
+
+impl Map3D<bool> {
+    /// Marks every point as `true`, in a map just big enough to hold them. Returns the map and
+    /// where its corner was, since points may be negative.
+    pub fn from_points(points: &[Point3D]) -> (Self, Point3D) {
+        let Some(bounds) = points.iter().map(|&p| Box3D::from_corners(p, p)).reduce(|a, b| a.union(&b)) else {
+            return (Self::new_filled(Point3D::ZERO, false), Point3D::ZERO);
+        };
+
+        let mut map = Self::new_filled(bounds.size(), false);
+        for &p in points {
+            map.set_tile(p - bounds.min(), true);
+        }
+        (map, bounds.min())
+    }
+
+    /// Parses `x,y,z` lines, see [`Map3D::from_points`].
+    pub fn parse_points(s: &str) -> Result<(Self, Point3D), ParseMap3DError> {
+        let points = s
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| l.parse::<Point3D>().map_err(|e| ParseMap3DError::PointParseError(i + 1, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_points(&points))
+    }
+}
+
+/// Parses layers like [`ByteMap`]s separated by blank lines, the first layer being `z = 0`.
+impl FromStr for Map3D<u8> {
+    type Err = ParseMap3DError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let layers = Sections::new(s)
+            .iter()
+            .enumerate()
+            .map(|(z, l)| ByteMap::from_str(l.text).map_err(|e| ParseMap3DError::LayerParseError(z, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let first = layers.first().ok_or(ParseMap3DError::EmptyString)?;
+        let size = Point3D(first.width() as isize, first.height() as isize, layers.len() as isize);
+        if let Some(z) = layers.iter().position(|l| l.size() != first.size()) {
+            return Err(ParseMap3DError::InconsistentLayerSize(z));
+        }
+
+        Ok(Self::from_fn(size, |p| layers[p.2 as usize].row(p.1 as usize)[p.0 as usize]))
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ParseMap3DError {
+    #[error("Can't parse an empty string to a Map3D")]
+    EmptyString,
+    #[error("Layer {0} has a different size than the first one")]
+    InconsistentLayerSize(usize),
+    #[error("Layer {0}: {1}")]
+    LayerParseError(usize, ParseMapError),
+    #[error("Point on line {0}: {1}")]
+    PointParseError(usize, ParsePointError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const DROPLET: &str = "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n3,2,5\n2,1,5\n2,3,5\n";
+
+    #[rstest]
+    #[case(Point3D(0, 0, 0), Some(0))]
+    #[case(Point3D(1, 2, 3), Some(1 + 4 * (2 + 3 * 3)))]
+    #[case(Point3D(4, 0, 0), None)]
+    #[case(Point3D(0, 0, -1), None)]
+    fn index_is_equal_to_expected(#[case] point: Point3D, #[case] expected: Option<usize>) {
+        let map = Map3D::new_filled(Point3D(4, 3, 5), 0u8);
+        assert_eq!(map.get_index(point), expected);
+    }
+
+    #[rstest]
+    #[case(Point3D(1, 1, 1), 6, 26)]
+    #[case(Point3D(0, 0, 0), 3, 7)]
+    #[case(Point3D(2, 1, 0), 4, 11)]
+    fn counts_neighbours(#[case] point: Point3D, #[case] faces: usize, #[case] all: usize) {
+        let map = Map3D::new_filled(Point3D(3, 3, 3), 0u8);
+
+        assert_eq!(map.neighbours_6(point).count(), faces);
+        assert_eq!(map.neighbours_26(point).count(), all);
+    }
+
+    #[test]
+    fn measures_surface_of_droplet() {
+        let (map, origin) = Map3D::parse_points(DROPLET).unwrap();
+
+        assert_eq!(origin, Point3D(1, 1, 1));
+        assert_eq!(map.surface_area(|&t| t), 64);
+        assert_eq!(map.exterior_surface_area(|&t| t), 58);
+        assert_eq!(map.enclosed_pockets(|&t| t), vec![vec![Point3D(1, 1, 4)]]);
+    }
+
+    #[test]
+    fn parses_layers() {
+        let map = Map3D::from_str("###\n#.#\n###\n\n###\n###\n###\n").unwrap();
+
+        assert_eq!(map.size(), Point3D(3, 3, 2));
+        assert_eq!(map.get_tile(Point3D(1, 1, 0)), Some(&b'.'));
+        assert_eq!(map.get_tile(Point3D(1, 1, 1)), Some(&b'#'));
+        assert_eq!(map.iter_with_points().filter(|(_, &t)| t == b'#').count(), 17);
+    }
+
+    #[test]
+    fn parse_points_reports_input_line() {
+        let result = Map3D::parse_points("1,1,1\n\n2,2,2\n3,x,3\n");
+        assert!(matches!(result, Err(ParseMap3DError::PointParseError(4, _))));
+    }
+
+    #[test]
+    fn parses_layers_with_crlf_and_extra_blank_lines() {
+        let map = Map3D::from_str("\r\n#.\r\n..\r\n\r\n\r\n.#\r\n##\r\n").unwrap();
+
+        assert_eq!(map.size(), Point3D(2, 2, 2));
+        assert_eq!(map.get_tile(Point3D(1, 0, 1)), Some(&b'#'));
+    }
+
+    #[rstest]
+    #[case("##\n##\n\n#\n", ParseMap3DError::InconsistentLayerSize(1))]
+    #[case("\n\n", ParseMap3DError::EmptyString)]
+    fn parse_layers_returns_errors(#[case] s: &str, #[case] expected: ParseMap3DError) {
+        let error = Map3D::from_str(s).unwrap_err();
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn flood_fills_through_open_tiles() {
+        let map = Map3D::from_str("..#\n###\n\n...\n...\n").unwrap();
+
+        assert_eq!(map.flood_fill(Point3D(0, 0, 0), |&t| t == b'.').len(), 8);
+        assert!(map.flood_fill(Point3D(2, 0, 0), |&t| t == b'.').is_empty());
+    }
+}
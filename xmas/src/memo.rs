@@ -0,0 +1,147 @@
+use std::{borrow::Borrow, collections::HashMap, fmt::Display, hash::Hash};
+
+/// Cache for recursive functions. The compute closure receives the memo back so it can recurse through it.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    stats: MemoStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self { cache: HashMap::new(), stats: MemoStats::default() }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self, &K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self, &key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Same as [`Memo::get_or_compute`], but the key is only turned into an owned one on a miss.
+    pub fn get_or_compute_borrowed<Q>(&mut self, key: &Q, compute: impl FnOnce(&mut Self, &Q) -> V) -> V
+        where K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized
+    {
+        if let Some(value) = self.cache.get(key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute(self, key);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+        where K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized
+    {
+        self.cache.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.cache.insert(key, value)
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = MemoStats::default();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl MemoStats {
+    pub fn lookups(&self) -> usize {
+        self.hits + self.misses
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 { 0.0 } else { self.hits as f64 / self.lookups() as f64 }
+    }
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} hits, {} misses ({:.1}% hit rate)", self.hits, self.misses, self.hit_rate() * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(n, |memo, &n| {
+            if n < 2 { n } else { fibonacci(n - 1, memo) + fibonacci(n - 2, memo) }
+        })
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 1)]
+    #[case(10, 55)]
+    #[case(90, 2880067194370816120)]
+    fn computes_recursively(#[case] n: u64, #[case] expected: u64) {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(n, &mut memo), expected);
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        fibonacci(10, &mut memo);
+
+        assert_eq!(memo.stats(), MemoStats { hits: 8, misses: 11 });
+
+        fibonacci(10, &mut memo);
+        assert_eq!(memo.stats().hits, 9);
+    }
+
+    #[test]
+    fn borrowed_keys_only_allocate_on_miss() {
+        let mut memo: Memo<Vec<u8>, usize> = Memo::new();
+        let mut calls = 0;
+        for _ in 0..3 {
+            let value = memo.get_or_compute_borrowed([1, 2, 3].as_slice(), |_, key| {
+                calls += 1;
+                key.len()
+            });
+            assert_eq!(value, 3);
+        }
+
+        assert_eq!(calls, 1);
+        assert_eq!(memo.stats(), MemoStats { hits: 2, misses: 1 });
+    }
+}
@@ -1,4 +1,4 @@
-use std::ops::{Add, Rem};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
 
 pub fn wrap_val<T>(val: T, range: T) -> T
@@ -8,6 +8,233 @@ pub fn wrap_val<T>(val: T, range: T) -> T
     if wrapped >= Default::default() { wrapped } else { wrapped + range }
 }
 
+/// Primitive integers, signed or not.
+pub trait Integer:
+    Copy + Ord + Default
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `self * rhs` wrapped into `0..modulus`, without overflowing.
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self;
+
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($wide:ty => $($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+                wrap_val(self as $wide * rhs as $wide, modulus as $wide) as Self
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+    )*};
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+                peasant_mul_mod(self, rhs, modulus)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+    )*};
+}
+
+impl_integer!(i128 => i8, i16, i32, i64, isize);
+impl_integer!(u128 => u8, u16, u32, u64, usize);
+impl_integer!(i128, u128);
+
+/// Multiplies by doubling and adding, for types that have nothing wider to multiply in.
+fn peasant_mul_mod<T: Integer>(a: T, b: T, modulus: T) -> T {
+    let two = T::ONE + T::ONE;
+    let add_mod = |x: T, y: T| if x >= modulus - y { x - (modulus - y) } else { x + y };
+
+    let (mut a, mut b) = (wrap_val(a, modulus), wrap_val(b, modulus));
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % two == T::ONE {
+            result = add_mod(result, a);
+        }
+        a = add_mod(a, a);
+        b = b / two;
+    }
+    result
+}
+
+fn abs<T: Integer>(value: T) -> T {
+    if value < T::ZERO { T::ZERO - value } else { value }
+}
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    abs(a)
+}
+
+/// Least common multiple, never negative. It's 0 if either is 0.
+/// Panics if it doesn't fit in `T`, even in release builds.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    abs((a / gcd(a, b)).checked_mul(b).expect("lcm overflowed"))
+}
+
+/// 0 when there are no values.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// 1 when there are no values.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, `g` being the gcd.
+pub fn extended_gcd<T>(a: T, b: T) -> (T, T, T)
+    where T: Integer + Neg<Output = T>
+{
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::ZERO { (-old_r, -old_x, -old_y) } else { (old_r, old_x, old_y) }
+}
+
+/// `base` to the power of `exp`, wrapped into `0..modulus`.
+/// Panics if `modulus` isn't positive or `exp` is negative.
+pub fn mod_pow<T: Integer>(base: T, exp: T, modulus: T) -> T {
+    assert!(modulus > T::ZERO, "modulus should be positive");
+    assert!(exp >= T::ZERO, "exponent shouldn't be negative");
+    let two = T::ONE + T::ONE;
+    let (mut base, mut exp) = (wrap_val(base, modulus), exp);
+    let mut result = wrap_val(T::ONE, modulus);
+    while exp > T::ZERO {
+        if exp % two == T::ONE {
+            result = result.mul_mod(base, modulus);
+        }
+        base = base.mul_mod(base, modulus);
+        exp = exp / two;
+    }
+    result
+}
+
+/// `x` in `0..modulus` such that `a * x` is 1 modulo `modulus`, if `a` and `modulus` are coprime.
+/// `None` if `modulus` isn't positive.
+pub fn mod_inv<T>(a: T, modulus: T) -> Option<T>
+    where T: Integer + Neg<Output = T>
+{
+    if modulus <= T::ZERO {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(wrap_val(a, modulus), modulus);
+    (g == T::ONE).then(|| wrap_val(x, modulus))
+}
+
+/// Solves `x ≡ remainder (mod modulus)` for every `(remainder, modulus)` pair. Moduli don't need
+/// to be coprime, but have to be positive. Returns the smallest non-negative `x` and the lcm of the
+/// moduli, or `None` when the congruences contradict each other, a modulus isn't positive or the
+/// lcm doesn't fit in `T`.
+pub fn crt<T>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)>
+    where T: Integer + Neg<Output = T>
+{
+    congruences.into_iter().try_fold((T::ZERO, T::ONE), |(r1, m1), (r2, m2)| {
+        if m2 <= T::ZERO {
+            return None;
+        }
+        let r2 = wrap_val(r2, m2);
+        let g = gcd(m1, m2);
+        let diff = r2 - r1;
+        if diff % g != T::ZERO {
+            return None;
+        }
+
+        // r1 + m1 * k ≡ r2 (mod m2), solved for k modulo m2 / g
+        let step = m2 / g;
+        let k = wrap_val(diff / g, step).mul_mod(mod_inv(m1 / g, step)?, step);
+        let modulus = (m1 / g).checked_mul(m2)?;
+        Some((wrap_val(r1 + m1 * k, modulus), modulus))
+    })
+}
+
+/// Largest `x` such that `x * x <= n`. Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    assert!(n >= T::ZERO, "can't take the square root of a negative number");
+    let two = T::ONE + T::ONE;
+    if n < two {
+        return n;
+    }
+
+    let mut x = n / two + T::ONE;
+    let mut y = (x + n / x) / two;
+    while y < x {
+        x = y;
+        y = (x + n / x) / two;
+    }
+    x
+}
+
+/// Sieve of Eratosthenes, primes up to `limit` included.
+pub fn primes_up_to(limit: usize) -> Vec<usize> {
+    let mut is_prime = vec![true; limit + 1];
+    let mut primes = Vec::new();
+    for n in 2..=limit {
+        if !is_prime[n] {
+            continue;
+        }
+        primes.push(n);
+        for multiple in (n * n..=limit).step_by(n) {
+            is_prime[multiple] = false;
+        }
+    }
+    primes
+}
+
+/// Prime factors of `n` and how many times each divides it, smallest first. Empty for `n <= 1`.
+pub fn factorise<T: Integer>(n: T) -> Vec<(T, u32)> {
+    let mut factors = Vec::new();
+    let mut n = n;
+    let mut divisor = T::ONE + T::ONE;
+    while divisor <= n / divisor {
+        let mut count = 0;
+        while n % divisor == T::ZERO {
+            n = n / divisor;
+            count += 1;
+        }
+        if count > 0 {
+            factors.push((divisor, count));
+        }
+        divisor = divisor + T::ONE;
+    }
+
+    if n > T::ONE {
+        factors.push((n, 1));
+    }
+    factors
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -29,4 +256,162 @@ mod tests {
         let result = wrap_val(val, range);
         assert_eq!(result, expected)
     }
+
+    #[rstest]
+    #[case(12, 18, 6, 36)]
+    #[case(-4, 6, 2, 12)]
+    #[case(7, 13, 1, 91)]
+    #[case(0, 5, 5, 0)]
+    #[case(0, 0, 0, 0)]
+    fn finds_gcd_and_lcm(
+        #[case] a: i64,
+        #[case] b: i64,
+        #[case] expected_gcd: i64,
+        #[case] expected_lcm: i64,
+    ) {
+        assert_eq!(gcd(a, b), expected_gcd);
+        assert_eq!(lcm(a, b), expected_lcm);
+    }
+
+    #[rstest]
+    #[case(vec![], 0, 1)]
+    #[case(vec![8, 12, 20], 4, 120)]
+    #[case(vec![2, 3, 5, 7], 1, 210)]
+    fn folds_gcd_and_lcm(
+        #[case] values: Vec<u64>,
+        #[case] expected_gcd: u64,
+        #[case] expected_lcm: u64,
+    ) {
+        assert_eq!(gcd_all(values.iter().copied()), expected_gcd);
+        assert_eq!(lcm_all(values), expected_lcm);
+    }
+
+    #[rstest]
+    #[case(240, 46)]
+    #[case(-15, 35)]
+    #[case(17, 0)]
+    fn extended_gcd_finds_coefficients(
+        #[case] a: i32,
+        #[case] b: i32,
+    ) {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, gcd(a, b));
+        assert_eq!(a * x + b * y, g);
+    }
+
+    #[rstest]
+    #[case(4, 13, 497, 445)]
+    #[case(2, 0, 7, 1)]
+    #[case(-2, 3, 5, 2)]
+    #[case(5, 100, 1, 0)]
+    fn raises_powers(
+        #[case] base: i64,
+        #[case] exp: i64,
+        #[case] modulus: i64,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(mod_pow(base, exp, modulus), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus should be positive")]
+    fn raising_powers_needs_a_positive_modulus() {
+        mod_pow(2, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "exponent shouldn't be negative")]
+    fn raising_powers_needs_a_non_negative_exponent() {
+        mod_pow(2, -1, 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm overflowed")]
+    fn lcm_panics_on_overflow() {
+        lcm(u64::MAX, u64::MAX - 1);
+    }
+
+    #[test]
+    fn raises_powers_without_overflow() {
+        let modulus = u64::MAX - 58;
+        assert_eq!(mod_pow(3u64, modulus - 1, modulus), 1);
+
+        let mersenne = (1u128 << 127) - 1;
+        assert_eq!(mod_pow(3, mersenne - 1, mersenne), 1);
+        assert_eq!(mod_inv(-2i128, i128::MAX), Some(i128::MAX / 2));
+    }
+
+    #[rstest]
+    #[case(3, 11, Some(4))]
+    #[case(-3, 11, Some(7))]
+    #[case(10, 17, Some(12))]
+    #[case(6, 9, None)]
+    #[case(3, 0, None)]
+    #[case(3, -11, None)]
+    fn inverts_modulo(
+        #[case] a: i64,
+        #[case] modulus: i64,
+        #[case] expected: Option<i64>,
+    ) {
+        assert_eq!(mod_inv(a, modulus), expected);
+    }
+
+    #[rstest]
+    #[case(vec![(2, 3), (3, 5), (2, 7)], Some((23, 105)))]
+    #[case(vec![(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)], Some((1068781, 3162341)))]
+    #[case(vec![(2, 4), (4, 6)], Some((10, 12)))]
+    #[case(vec![(1, 4), (2, 6)], None)]
+    #[case(vec![(-1, 5)], Some((4, 5)))]
+    #[case(vec![], Some((0, 1)))]
+    #[case(vec![(1, 3), (0, 0)], None)]
+    #[case(vec![(1, -3)], None)]
+    #[case(vec![(0, i64::MAX), (0, i64::MAX - 1)], None)]
+    fn solves_congruences(
+        #[case] congruences: Vec<(i64, i64)>,
+        #[case] expected: Option<(i64, i64)>,
+    ) {
+        assert_eq!(crt(congruences), expected);
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 1)]
+    #[case(15, 3)]
+    #[case(16, 4)]
+    #[case(u64::MAX, u32::MAX as u64)]
+    fn finds_square_roots(
+        #[case] n: u64,
+        #[case] expected: u64,
+    ) {
+        assert_eq!(isqrt(n), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative number")]
+    fn square_roots_need_non_negative_numbers() {
+        isqrt(-4);
+    }
+
+    #[rstest]
+    #[case(1, vec![])]
+    #[case(2, vec![2])]
+    #[case(30, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29])]
+    fn sieves_primes(
+        #[case] limit: usize,
+        #[case] expected: Vec<usize>,
+    ) {
+        assert_eq!(primes_up_to(limit), expected);
+    }
+
+    #[rstest]
+    #[case(1, vec![])]
+    #[case(97, vec![(97, 1)])]
+    #[case(360, vec![(2, 3), (3, 2), (5, 1)])]
+    #[case(600851475143, vec![(71, 1), (839, 1), (1471, 1), (6857, 1)])]
+    fn factorises(
+        #[case] n: u64,
+        #[case] expected: Vec<(u64, u32)>,
+    ) {
+        assert_eq!(factorise(n), expected);
+    }
 }
@@ -0,0 +1,491 @@
+use std::{fmt::Display, ops::{Range, RangeInclusive}, str::FromStr};
+use thiserror::Error;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("{kind} @ line {line}, column {column}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    /// Error at a byte offset of the given input.
+    pub fn at(input: &str, offset: usize, kind: ParseErrorKind) -> Self {
+        let mut cursor = Cursor::new(input);
+        cursor.pos = offset;
+        cursor.error(kind)
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    #[error("Expected {0}")]
+    Expected(String),
+    #[error("Invalid integer \"{0}\"")]
+    InvalidInteger(String),
+    #[error("Unexpected trailing input \"{0}\"")]
+    TrailingInput(String),
+    #[error("{0}")]
+    Custom(String),
+}
+
+/// Position in some input text, which every combinator advances past what it parsed.
+/// Errors are reported with the 1-based line and column of the cursor.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+    first_line: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::at_line(input, 1)
+    }
+
+    /// Cursor for text that starts on the given line of a bigger input.
+    pub fn at_line(input: &'a str, line: usize) -> Self {
+        Self { input, pos: 0, first_line: line }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    pub fn line(&self) -> usize {
+        self.first_line + self.input[..self.pos].matches('\n').count()
+    }
+
+    pub fn column(&self) -> usize {
+        let line_start = self.input[..self.pos].rfind('\n').map_or(0, |i| i + 1);
+        self.input[line_start..self.pos].chars().count() + 1
+    }
+
+    pub fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError { line: self.line(), column: self.column(), kind }
+    }
+
+    pub fn custom_error(&self, message: impl Display) -> ParseError {
+        self.error(ParseErrorKind::Custom(message.to_string()))
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(|ch| ch.is_whitespace() && ch != '\n');
+    }
+
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|ch| !predicate(ch)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes the token if the input continues with it.
+    pub fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    pub fn expect(&mut self, token: &str) -> ParseResult<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::Expected(format!("\"{token}\""))))
+        }
+    }
+
+    /// Runs the parser, leaving the cursor untouched if it fails.
+    pub fn attempt<T>(&mut self, parser: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        let start = self.pos;
+        let result = parser(self);
+        if result.is_err() {
+            self.pos = start;
+        }
+        result
+    }
+
+    pub fn finish(&self) -> ParseResult<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::TrailingInput(self.rest().to_string())))
+        }
+    }
+}
+
+/// Types with a default parser, so they can be read from lines and sections without passing one.
+pub trait Parse: Sized {
+    fn parse(cursor: &mut Cursor) -> ParseResult<Self>;
+}
+
+macro_rules! impl_parse_int {
+    ($parser:ident: $($t:ty),*) => {
+        $(impl Parse for $t {
+            fn parse(cursor: &mut Cursor) -> ParseResult<Self> {
+                $parser(cursor)
+            }
+        })*
+    };
+}
+
+impl_parse_int!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_parse_int!(signed: i8, i16, i32, i64, i128, isize);
+
+impl<T: Parse> Parse for RangeInclusive<T> {
+    fn parse(cursor: &mut Cursor) -> ParseResult<Self> {
+        let start = T::parse(cursor)?;
+        cursor.expect("-")?;
+        let end = T::parse(cursor)?;
+        Ok(start..=end)
+    }
+}
+
+impl Parse for String {
+    fn parse(cursor: &mut Cursor) -> ParseResult<Self> {
+        Ok(cursor.take_while(|ch| ch != '\n').to_string())
+    }
+}
+
+/// Parses the whole string, failing if anything is left after the parser.
+pub fn parse_str<'a, T>(s: &'a str, parser: impl FnOnce(&mut Cursor<'a>) -> ParseResult<T>) -> ParseResult<T> {
+    let mut cursor = Cursor::new(s);
+    let value = parser(&mut cursor)?;
+    cursor.finish()?;
+    Ok(value)
+}
+
+/// Parses each non-empty line on its own, so errors point at the right line.
+pub fn parse_lines<'a, T>(
+    s: &'a str,
+    mut parser: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let mut cursor = Cursor::at_line(line, i + 1);
+            let value = parser(&mut cursor)?;
+            cursor.finish()?;
+            Ok(value)
+        })
+        .collect()
+}
+
+pub fn unsigned<T: FromStr>(cursor: &mut Cursor) -> ParseResult<T> {
+    let start = cursor.clone();
+    let digits = cursor.take_while(|ch| ch.is_ascii_digit());
+    if digits.is_empty() {
+        return Err(start.error(ParseErrorKind::Expected("digit".to_string())));
+    }
+    digits.parse().map_err(|_| start.error(ParseErrorKind::InvalidInteger(digits.to_string())))
+}
+
+pub fn signed<T: FromStr>(cursor: &mut Cursor) -> ParseResult<T> {
+    let start = cursor.clone();
+    let sign_len = usize::from(cursor.eat("-") || cursor.eat("+"));
+    let digits = cursor.take_while(|ch| ch.is_ascii_digit());
+    if digits.is_empty() {
+        let mut at_digit = start.clone();
+        at_digit.pos += sign_len;
+        return Err(at_digit.error(ParseErrorKind::Expected("digit".to_string())));
+    }
+
+    let number = &start.rest()[..sign_len + digits.len()];
+    number.parse().map_err(|_| start.error(ParseErrorKind::InvalidInteger(number.to_string())))
+}
+
+/// One or more items separated by `separator`. Whitespace around separators is skipped.
+pub fn separated<'a, T>(
+    cursor: &mut Cursor<'a>,
+    separator: &str,
+    mut item: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let mut items = vec![item(cursor)?];
+    loop {
+        let before = cursor.pos;
+        if !cursor.eat(separator) {
+            cursor.skip_whitespace();
+            if !cursor.eat(separator) {
+                cursor.pos = before;
+                return Ok(items);
+            }
+        }
+        cursor.skip_whitespace();
+        items.push(item(cursor)?);
+    }
+}
+
+/// Zero or more whitespace separated items. Stops at the first item that fails without consuming
+/// anything, while an item failing halfway through is reported as an error.
+pub fn many<'a, T>(
+    cursor: &mut Cursor<'a>,
+    mut item: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    let mut items = Vec::new();
+    loop {
+        let before = cursor.pos;
+        cursor.skip_whitespace();
+        let item_start = cursor.pos;
+        match item(cursor) {
+            Ok(value) => items.push(value),
+            Err(_) if cursor.pos == item_start => {
+                cursor.pos = before;
+                return Ok(items);
+            },
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+pub fn delimited<'a, T>(
+    cursor: &mut Cursor<'a>,
+    open: &str,
+    close: &str,
+    inner: impl FnOnce(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<T> {
+    cursor.expect(open)?;
+    let value = inner(cursor)?;
+    cursor.expect(close)?;
+    Ok(value)
+}
+
+/// `(a,b,c)`
+pub fn parenthesized<'a, T>(
+    cursor: &mut Cursor<'a>,
+    item: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    delimited(cursor, "(", ")", |c| separated(c, ",", item))
+}
+
+/// `{a,b,c}`
+pub fn braced<'a, T>(
+    cursor: &mut Cursor<'a>,
+    item: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<Vec<T>> {
+    delimited(cursor, "{", "}", |c| separated(c, ",", item))
+}
+
+/// `[...]`, returning everything between the brackets as is.
+pub fn bracketed<'a>(cursor: &mut Cursor<'a>) -> ParseResult<&'a str> {
+    delimited(cursor, "[", "]", |c| Ok(c.take_while(|ch| ch != ']' && ch != '\n')))
+}
+
+/// `key: value value value`
+pub fn key_values<'a, T>(
+    cursor: &mut Cursor<'a>,
+    mut value: impl FnMut(&mut Cursor<'a>) -> ParseResult<T>,
+) -> ParseResult<(&'a str, Vec<T>)> {
+    let key = cursor.take_while(|ch| ch != ':' && ch != '\n').trim();
+    cursor.expect(":")?;
+
+    let mut values = Vec::new();
+    loop {
+        cursor.skip_whitespace();
+        if cursor.is_empty() || cursor.peek() == Some('\n') {
+            return Ok((key, values));
+        }
+        values.push(value(cursor)?);
+    }
+}
+
+/// `a-b`, both ends included.
+pub fn range<T: FromStr>(cursor: &mut Cursor) -> ParseResult<RangeInclusive<T>> {
+    let start = unsigned(cursor)?;
+    cursor.expect("-")?;
+    let end = unsigned(cursor)?;
+    Ok(start..=end)
+}
+
+/// Word made of anything but whitespace and the given delimiters.
+pub fn word<'a>(cursor: &mut Cursor<'a>, delimiters: &[char]) -> ParseResult<&'a str> {
+    let word = cursor.take_while(|ch| !ch.is_whitespace() && !delimiters.contains(&ch));
+    if word.is_empty() {
+        Err(cursor.error(ParseErrorKind::Expected("word".to_string())))
+    } else {
+        Ok(word)
+    }
+}
+
+/// Every integer in the string, in order, ignoring whatever is around them.
+/// A `-` or `+` right before the digits counts as a sign unless it follows a letter or digit, so `3-5`
+/// gives `3` and `5` while `x=-3` gives `-3`. Unsigned types never take the sign.
+pub fn ints<T: FromStr>(s: &str) -> ParseResult<Vec<T>> {
+    Ok(ints_spanned(s)?.into_iter().map(|n| n.value).collect())
+}
+
+/// Integer found by [`ints_spanned`], along with where it was and what came before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpannedInt<'a, T> {
+    pub value: T,
+    pub span: Range<usize>,
+    /// Text between the previous integer (or the start) and this one
+    pub prefix: &'a str,
+}
+
+/// Same as [`ints`], but keeping the byte span and the text before each integer,
+/// like the `R` in `R12`.
+pub fn ints_spanned<T: FromStr>(s: &str) -> ParseResult<Vec<SpannedInt<'_, T>>> {
+    let bytes = s.as_bytes();
+    let mut numbers = Vec::new();
+    let mut previous_end = 0;
+    let mut i = 0;
+
+    // Unsigned types skip the sign entirely, else `+12` would parse with the `+` in its span
+    let signed = "-1".parse::<T>().is_ok();
+
+    while i < bytes.len() {
+        let has_sign = signed
+            && matches!(bytes[i], b'-' | b'+')
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+        if !has_sign && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let digits_start = if has_sign { i + 1 } else { i };
+        let end = digits_start + bytes[digits_start..].iter().take_while(|b| b.is_ascii_digit()).count();
+        let Ok(value) = s[start..end].parse::<T>() else {
+            return Err(ParseError::at(s, start, ParseErrorKind::InvalidInteger(s[start..end].to_string())));
+        };
+
+        numbers.push(SpannedInt { value, span: start..end, prefix: &s[previous_end..start] });
+        previous_end = end;
+        i = end;
+    }
+
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("42", 42)]
+    #[case("-17", -17)]
+    #[case("+3", 3)]
+    fn parses_signed_integers(#[case] s: &str, #[case] expected: i32) {
+        assert_eq!(parse_str(s, signed::<i32>), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("", 1, ParseErrorKind::Expected("digit".to_string()))]
+    #[case("-x", 2, ParseErrorKind::Expected("digit".to_string()))]
+    #[case("300", 1, ParseErrorKind::InvalidInteger("300".to_string()))]
+    #[case("12a", 3, ParseErrorKind::TrailingInput("a".to_string()))]
+    fn reports_integer_errors(#[case] s: &str, #[case] column: usize, #[case] kind: ParseErrorKind) {
+        assert_eq!(parse_str(s, signed::<i8>), Err(ParseError { line: 1, column, kind }));
+    }
+
+    #[test]
+    fn parses_groups() {
+        let result = parse_str("[.##.] (3) (1,3) {3, 5,4}", |c| {
+            let lights = bracketed(c)?;
+            c.skip_whitespace();
+            let buttons = many(c, |c| parenthesized(c, unsigned::<usize>))?;
+            c.skip_whitespace();
+            let joltages = braced(c, unsigned::<u16>)?;
+            Ok((lights, buttons, joltages))
+        });
+
+        assert_eq!(result, Ok((".##.", vec![vec![3], vec![1, 3]], vec![3, 5, 4])));
+    }
+
+    #[test]
+    fn many_reports_errors_inside_items() {
+        let result = parse_str("(1) (2;3)", |c| many(c, |c| parenthesized(c, unsigned::<u8>)));
+        assert_eq!(result.unwrap_err().column, 7);
+    }
+
+    #[test]
+    fn parses_key_values() {
+        let result = parse_str("aaa: you hhh", |c| key_values(c, |c| word(c, &[])));
+        assert_eq!(result, Ok(("aaa", vec!["you", "hhh"])));
+    }
+
+    #[test]
+    fn parses_ranges_per_line() {
+        let result = parse_lines("3-5\n10-14\n\n16-20\n", range::<u64>);
+        assert_eq!(result, Ok(vec![3..=5, 10..=14, 16..=20]));
+    }
+
+    #[test]
+    fn reports_line_and_column() {
+        let result = parse_lines("(1,2)\n(3,4)\n(5;6)\n", |c| parenthesized(c, unsigned::<u8>));
+        let error = result.unwrap_err();
+
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.to_string(), "Expected \")\" @ line 3, column 3");
+    }
+
+    #[rstest]
+    #[case("3-5", 3..=5)]
+    #[case("-3--1", -3..=-1)]
+    #[case("-2-4", -2..=4)]
+    fn parses_signed_ranges(#[case] s: &str, #[case] expected: RangeInclusive<i32>) {
+        assert_eq!(parse_str(s, RangeInclusive::<i32>::parse), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("(1,-2) {3, 4}", vec![1, -2, 3, 4])]
+    #[case("x=-3, y=+12", vec![-3, 12])]
+    #[case("3-5 a-1 --2", vec![3, 5, 1, -2])]
+    #[case("no numbers", vec![])]
+    fn extracts_signed_ints(#[case] s: &str, #[case] expected: Vec<i64>) {
+        assert_eq!(ints::<i64>(s), Ok(expected));
+    }
+
+    #[test]
+    fn unsigned_ints_skip_signs() {
+        assert_eq!(ints::<u32>("x=-3, y=+12, 3-5"), Ok(vec![3, 12, 3, 5]));
+    }
+
+    #[test]
+    fn unsigned_spans_leave_signs_in_the_prefix() {
+        let numbers = ints_spanned::<u32>("+12 -3").unwrap();
+
+        assert_eq!(numbers, vec![
+            SpannedInt { value: 12, span: 1..3, prefix: "+" },
+            SpannedInt { value: 3, span: 5..6, prefix: " -" },
+        ]);
+    }
+
+    #[test]
+    fn ints_report_overflow_position() {
+        let error = ints::<u8>("1\n2 300").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn keeps_spans_and_prefixes() {
+        let numbers = ints_spanned::<i32>("R12\nL-3, x").unwrap();
+
+        assert_eq!(numbers, vec![
+            SpannedInt { value: 12, span: 1..3, prefix: "R" },
+            SpannedInt { value: 3, span: 6..7, prefix: "\nL-" },
+        ]);
+    }
+
+    #[test]
+    fn tracks_lines_in_multiline_input() {
+        let result = parse_str("1\n2\nx", |c| separated(c, "\n", unsigned::<u8>));
+        assert_eq!(result.unwrap_err().line, 3);
+    }
+}
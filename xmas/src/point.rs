@@ -0,0 +1,301 @@
+use std::{fmt::Display, num::ParseIntError, ops, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{point2d::Point2D, point3d::Point3D};
+
+/// Vector of any dimension. [`Point2D`] and [`Point3D`] convert to and from it, so code that works
+/// on any dimension can be written once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Point<const N: usize, T = isize>(pub [T; N]);
+
+pub type Point4D<T = isize> = Point<4, T>;
+
+impl<const N: usize, T: Copy> Point<N, T> {
+    pub fn new(components: [T; N]) -> Self {
+        Self(components)
+    }
+
+    pub fn components(&self) -> [T; N] {
+        self.0
+    }
+
+    pub fn map<U>(&self, f: impl Fn(T) -> U) -> Point<N, U> {
+        Point(self.0.map(f))
+    }
+
+    /// Combines matching components of both points.
+    pub fn zip_with(&self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        Self(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+
+    pub fn scale(&self, other: Self) -> Self
+        where T: ops::Mul<Output = T>
+    {
+        self.zip_with(other, |a, b| a * b)
+    }
+
+    pub fn min(&self, other: Self) -> Self
+        where T: Ord
+    {
+        self.zip_with(other, Ord::min)
+    }
+
+    pub fn max(&self, other: Self) -> Self
+        where T: Ord
+    {
+        self.zip_with(other, Ord::max)
+    }
+
+    pub fn dot(&self, other: Self) -> T
+        where T: Default + ops::Add<Output = T> + ops::Mul<Output = T>
+    {
+        self.0.iter().zip(other.0).fold(T::default(), |sum, (&a, b)| sum + a * b)
+    }
+
+    pub fn sqr_magnitude(&self) -> T
+        where T: Default + ops::Add<Output = T> + ops::Mul<Output = T>
+    {
+        self.dot(*self)
+    }
+}
+
+macro_rules! impl_signed_point {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl<const N: usize> Point<N, $t> {
+                pub const ZERO: Self = Self([0; N]);
+
+                pub fn manhattan_magnitude(&self) -> $u {
+                    self.0.iter().map(|c| c.unsigned_abs()).sum()
+                }
+
+                pub fn manhattan_distance(&self, towards: Self) -> $u {
+                    (*self - towards).manhattan_magnitude()
+                }
+            }
+        )*
+    };
+}
+
+impl_signed_point!(isize => usize, i64 => u64, i32 => u32);
+
+impl<const N: usize, T: Copy + Default> Default for Point<N, T> {
+    fn default() -> Self {
+        Self([T::default(); N])
+    }
+}
+
+impl<const N: usize, T> ops::Index<usize> for Point<N, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize, T> ops::IndexMut<usize> for Point<N, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+macro_rules! impl_point_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl<const N: usize, T: Copy + ops::$trait<Output = T>> ops::$trait for Point<N, T> {
+            type Output = Self;
+
+            fn $fn(self, rhs: Self) -> Self::Output {
+                self.zip_with(rhs, |a, b| a $op b)
+            }
+        }
+
+        impl<const N: usize, T: Copy + ops::$trait<Output = T>> ops::$assign_trait for Point<N, T> {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
+
+impl_point_op!(Add, add, AddAssign, add_assign, +);
+impl_point_op!(Sub, sub, SubAssign, sub_assign, -);
+
+macro_rules! impl_point_scalar_op {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl<const N: usize, T: Copy + ops::$trait<Output = T>> ops::$trait<T> for Point<N, T> {
+            type Output = Self;
+
+            fn $fn(self, rhs: T) -> Self::Output {
+                self.map(|c| c $op rhs)
+            }
+        }
+
+        impl<const N: usize, T: Copy + ops::$trait<Output = T>> ops::$assign_trait<T> for Point<N, T> {
+            fn $assign_fn(&mut self, rhs: T) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
+
+impl_point_scalar_op!(Mul, mul, MulAssign, mul_assign, *);
+impl_point_scalar_op!(Div, div, DivAssign, div_assign, /);
+impl_point_scalar_op!(Rem, rem, RemAssign, rem_assign, %);
+
+/// Scalar `*`, `/` and `%` for the fixed size points, done through [`Point`].
+macro_rules! forward_scalar_ops {
+    ($point:ty) => {
+        $crate::point::forward_scalar_ops!($point: Mul, mul, MulAssign, mul_assign);
+        $crate::point::forward_scalar_ops!($point: Div, div, DivAssign, div_assign);
+        $crate::point::forward_scalar_ops!($point: Rem, rem, RemAssign, rem_assign);
+    };
+    ($point:ty: $trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident) => {
+        impl std::ops::$trait<isize> for $point {
+            type Output = Self;
+
+            fn $fn(self, rhs: isize) -> Self::Output {
+                std::ops::$trait::$fn($crate::point::Point::from(self), rhs).into()
+            }
+        }
+
+        impl std::ops::$assign_trait<isize> for $point {
+            fn $assign_fn(&mut self, rhs: isize) {
+                *self = std::ops::$trait::$fn(*self, rhs)
+            }
+        }
+    };
+}
+
+pub(crate) use forward_scalar_ops;
+
+impl<const N: usize, T: Copy + ops::Neg<Output = T>> ops::Neg for Point<N, T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|c| -c)
+    }
+}
+
+impl<const N: usize, T> From<[T; N]> for Point<N, T> {
+    fn from(value: [T; N]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Point2D> for Point<2> {
+    fn from(value: Point2D) -> Self {
+        Self([value.0, value.1])
+    }
+}
+
+impl From<Point<2>> for Point2D {
+    fn from(value: Point<2>) -> Self {
+        Self(value.0[0], value.0[1])
+    }
+}
+
+impl From<Point3D> for Point<3> {
+    fn from(value: Point3D) -> Self {
+        Self([value.0, value.1, value.2])
+    }
+}
+
+impl From<Point<3>> for Point3D {
+    fn from(value: Point<3>) -> Self {
+        Self(value.0[0], value.0[1], value.0[2])
+    }
+}
+
+impl<const N: usize, T: Display> Display for Point<N, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{c}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Parses comma separated components, like `1,-2,3`.
+impl<const N: usize, T: FromStr<Err = ParseIntError>> FromStr for Point<N, T> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let components = s
+            .split(',')
+            .map(|c| c.trim().parse::<T>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let found = components.len();
+
+        components
+            .try_into()
+            .map(Self)
+            .map_err(|_| ParsePointError::InvalidLength { expected: N, found })
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ParsePointError {
+    #[error("Expected {expected} components, found {found}")]
+    InvalidLength { expected: usize, found: usize },
+    #[error("Couldn't parse component: {0}")]
+    ParseInt(#[from] ParseIntError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn applies_operators_per_component() {
+        let a = Point4D::new([1, -2, 3, 4]);
+        let b = Point4D::new([5, 6, -7, 8]);
+
+        assert_eq!(a + b, Point([6, 4, -4, 12]));
+        assert_eq!(a - b, Point([-4, -8, 10, -4]));
+        assert_eq!(-a, Point([-1, 2, -3, -4]));
+        assert_eq!(b * 2, Point([10, 12, -14, 16]));
+        assert_eq!(b / 2, Point([2, 3, -3, 4]));
+        assert_eq!(b % 3, Point([2, 0, -1, 2]));
+        assert_eq!(a.scale(b), Point([5, -12, -21, 32]));
+        assert_eq!(a.min(b), Point([1, -2, -7, 4]));
+        assert_eq!(a.max(b), Point([5, 6, 3, 8]));
+    }
+
+    #[rstest]
+    #[case(Point([1, 2, 3]), Point([4, -5, 6]), 12)]
+    #[case(Point([0, 0, 0]), Point([4, -5, 6]), 0)]
+    #[case(Point([-1, 0, 2]), Point([-1, 9, 2]), 5)]
+    fn computes_dot_product(#[case] a: Point<3>, #[case] b: Point<3>, #[case] expected: isize) {
+        assert_eq!(a.dot(b), expected);
+    }
+
+    #[test]
+    fn computes_magnitudes() {
+        let p = Point::<4, i64>::new([1, -2, 3, -4]);
+
+        assert_eq!(p.manhattan_magnitude(), 10);
+        assert_eq!(p.sqr_magnitude(), 30);
+        assert_eq!(p.manhattan_distance(Point4D::<i64>::ZERO), 10);
+    }
+
+    #[rstest]
+    #[case("1,2,3,4", Ok(Point([1, 2, 3, 4])))]
+    #[case(" -1, 0 ,5, 9", Ok(Point([-1, 0, 5, 9])))]
+    #[case("1,2,3", Err(ParsePointError::InvalidLength { expected: 4, found: 3 }))]
+    fn parses_points(#[case] s: &str, #[case] expected: Result<Point4D, ParsePointError>) {
+        assert_eq!(s.parse::<Point4D>(), expected);
+    }
+
+    #[test]
+    fn converts_from_named_points() {
+        assert_eq!(Point2D::from(Point::from(Point2D(3, -4))), Point2D(3, -4));
+        assert_eq!(Point::from(Point3D(1, 2, 3)) * 2, Point([2, 4, 6]));
+        assert_eq!(Point([1, 2, 3]).to_string(), "(1, 2, 3)");
+    }
+}
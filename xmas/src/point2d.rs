@@ -1,6 +1,10 @@
-use std::{fmt::Display, num::ParseIntError, ops, str::FromStr};
-use crate::direction::Direction;
+use std::{fmt::Display, ops, str::FromStr};
+use crate::{
+    direction::{Direction, QuarterRotation},
+    point::{forward_scalar_ops, ParsePointError, Point},
+};
 
+/// Uses screen coordinates: x grows to the right and y grows downwards, like rows of a text map.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
 pub struct Point2D(pub isize, pub isize);
 
@@ -8,7 +12,7 @@ impl Point2D {
     pub const ZERO: Self = Point2D(0, 0);
 
     pub fn manhattan_magnitude(&self) -> usize {
-        self.0.unsigned_abs() + self.1.unsigned_abs()
+        Point::from(*self).manhattan_magnitude()
     }
 
     pub fn manhattan_distance(&self, towards: Point2D) -> usize {
@@ -16,22 +20,22 @@ impl Point2D {
     }
 
     pub fn sqr_magnitude(&self) -> usize {
-        (self.0 * self.0 + self.1 * self.1) as usize
+        Point::from(*self).sqr_magnitude() as usize
     }
 
     pub fn max(&self, other: Self) -> Self {
-        Point2D(self.0.max(other.0), self.1.max(other.1))
+        Point::from(*self).max(other.into()).into()
     }
 
     pub fn min(&self, other: Self) -> Self {
-        Point2D(self.0.min(other.0), self.1.min(other.1))
+        Point::from(*self).min(other.into()).into()
     }
 
     pub fn try_get_direction(&self) -> Option<(Direction, usize)> {
         match self {
             Point2D(0, 0) => None,
             Point2D(0, y) => Some((if *y > 0 { Direction::Down } else { Direction::Up }, y.unsigned_abs())),
-            Point2D(x, 0) => Some((if *x > 0 { Direction::Left } else { Direction::Right }, x.unsigned_abs())),
+            Point2D(x, 0) => Some((if *x > 0 { Direction::Right } else { Direction::Left }, x.unsigned_abs())),
             _ => None,
         }
     }
@@ -41,11 +45,26 @@ impl Point2D {
     }
     
     pub fn scale(&self, other: Point2D) -> Point2D {
-        Point2D(self.0 * other.0, self.1 * other.1)
+        Point::from(*self).scale(other.into()).into()
     }
 
     pub fn map(&self, f: impl Fn(isize) -> isize) -> Self {
-        Self(f(self.0), f(self.1))
+        Point::from(*self).map(f).into()
+    }
+
+    /// Rotates around the origin, `QuarterRotation::Right` being clockwise on screen.
+    pub fn rotate(&self, rot: QuarterRotation) -> Self {
+        let Point2D(x, y) = *self;
+        match rot {
+            QuarterRotation::None => *self,
+            QuarterRotation::Right => Point2D(-y, x),
+            QuarterRotation::TurnAround => Point2D(-x, -y),
+            QuarterRotation::Left => Point2D(y, -x),
+        }
+    }
+
+    pub fn rotate_around(&self, pivot: Point2D, rot: QuarterRotation) -> Self {
+        (*self - pivot).rotate(rot) + pivot
     }
 }
 
@@ -59,7 +78,7 @@ impl ops::Add<Point2D> for Point2D {
     type Output = Point2D;
 
     fn add(self, rhs: Point2D) -> Self::Output {
-        Point2D(self.0 + rhs.0, self.1 + rhs.1)
+        (Point::from(self) + Point::from(rhs)).into()
     }
 }
 
@@ -73,7 +92,7 @@ impl ops::Sub<Point2D> for Point2D {
     type Output = Point2D;
 
     fn sub(self, rhs: Point2D) -> Self::Output {
-        Point2D(self.0 - rhs.0, self.1 - rhs.1)
+        (Point::from(self) - Point::from(rhs)).into()
     }
 }
 
@@ -83,11 +102,13 @@ impl ops::SubAssign<Point2D> for Point2D {
     }
 }
 
-impl ops::Mul<isize> for Point2D {
+forward_scalar_ops!(Point2D);
+
+impl ops::Neg for Point2D {
     type Output = Self;
 
-    fn mul(self, rhs: isize) -> Self::Output {
-        Self(self.0 * rhs, self.1 * rhs)
+    fn neg(self) -> Self::Output {
+        (-Point::from(self)).into()
     }
 }
 
@@ -103,28 +124,75 @@ impl From<[isize; 2]> for Point2D {
     }
 }
 
+/// Used to fail with a bare [`std::num::ParseIntError`], which is now [`ParsePointError::ParseInt`].
+/// A wrong amount of components is reported too, instead of panicking.
 impl FromStr for Point2D {
-    type Err = ParseIntError;
+    type Err = ParsePointError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // TODO: throw proper errors
+        Point::<2>::from_str(s).map(Self::from)
+    }
+}
 
-        let mut sections = s.split(',');
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::DIRECTIONS;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Point2D(3, 0), Some((Direction::Right, 3)))]
+    #[case(Point2D(-2, 0), Some((Direction::Left, 2)))]
+    #[case(Point2D(0, 5), Some((Direction::Down, 5)))]
+    #[case(Point2D(0, -1), Some((Direction::Up, 1)))]
+    #[case(Point2D(1, 1), None)]
+    #[case(Point2D(0, 0), None)]
+    fn gets_direction(#[case] point: Point2D, #[case] expected: Option<(Direction, usize)>) {
+        assert_eq!(point.try_get_direction(), expected);
+    }
+
+    #[rstest]
+    #[case(QuarterRotation::None, Point2D(3, 1))]
+    #[case(QuarterRotation::Right, Point2D(-1, 3))]
+    #[case(QuarterRotation::TurnAround, Point2D(-3, -1))]
+    #[case(QuarterRotation::Left, Point2D(1, -3))]
+    fn rotates_around_origin(#[case] rot: QuarterRotation, #[case] expected: Point2D) {
+        assert_eq!(Point2D(3, 1).rotate(rot), expected);
+        assert_eq!(Point2D(13, 11).rotate_around(Point2D(10, 10), rot), expected + Point2D(10, 10));
+    }
+
+    #[rstest]
+    fn rotates_like_directions(#[values(QuarterRotation::Right, QuarterRotation::Left)] rot: QuarterRotation) {
+        for direction in DIRECTIONS {
+            assert_eq!(direction.as_point().rotate(rot), direction.turn(rot).as_point());
+        }
+    }
 
-        let x = sections
-            .next()
-            .expect("missing x component")
-            .parse::<isize>()?;
+    #[rstest]
+    #[case("3,-4", Ok(Point2D(3, -4)))]
+    #[case(" 10 , 2 ", Ok(Point2D(10, 2)))]
+    #[case("1,2,3", Err(ParsePointError::InvalidLength { expected: 2, found: 3 }))]
+    #[case("7", Err(ParsePointError::InvalidLength { expected: 2, found: 1 }))]
+    fn parses_points(#[case] s: &str, #[case] expected: Result<Point2D, ParsePointError>) {
+        assert_eq!(s.parse::<Point2D>(), expected);
+    }
 
-        let y = sections
-            .next()
-            .expect("missing y component")
-            .parse::<isize>()?;
+    #[test]
+    fn forwards_scalar_ops() {
+        let mut point = Point2D(7, -9);
+        point *= 2;
 
-        if sections.next().is_some() {
-            panic!("more than 2 sections for Point2D");
-        }
+        assert_eq!(point, Point2D(14, -18));
+        assert_eq!(point / 4, Point2D(3, -4));
+        assert_eq!(point % 4, Point2D(2, -2));
+    }
 
-        Ok(Point2D(x, y))
+    #[rstest]
+    fn direction_round_trips(#[values(1, 2, 7)] distance: isize) {
+        for direction in DIRECTIONS {
+            let point = direction.as_point() * distance;
+            assert_eq!(point.try_get_direction(), Some((direction, distance as usize)));
+            assert_eq!(Point2D::ZERO.try_get_direction_towards(point), Some((direction, distance as usize)));
+        }
     }
 }
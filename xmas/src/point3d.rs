@@ -1,6 +1,6 @@
-use std::{fmt::Display, num::ParseIntError, ops, str::FromStr};
+use std::{fmt::Display, ops, str::FromStr};
 
-use thiserror::Error;
+use crate::point::{forward_scalar_ops, ParsePointError, Point};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Hash)]
 pub struct Point3D(pub isize, pub isize, pub isize);
@@ -9,7 +9,7 @@ impl Point3D {
     pub const ZERO: Self = Self(0, 0, 0);
 
     pub fn manhattan_magnitude(&self) -> usize {
-        self.0.unsigned_abs() + self.1.unsigned_abs() + self.2.unsigned_abs()
+        Point::from(*self).manhattan_magnitude()
     }
 
     pub fn manhattan_distance(&self, towards: Self) -> usize {
@@ -17,22 +17,23 @@ impl Point3D {
     }
 
     pub fn sqr_magnitude(&self) -> usize {
-        (self.0 * self.0 + self.1 * self.1 + self.2 * self.2) as usize
+        Point::from(*self).sqr_magnitude() as usize
     }
 
     pub fn max(&self, other: Self) -> Self {
-        Self(self.0.max(other.0), self.1.max(other.1), self.2.max(other.2))
+        Point::from(*self).max(other.into()).into()
     }
 
     pub fn min(&self, other: Self) -> Self {
-        Self(self.0.min(other.0), self.1.min(other.1), self.2.min(other.2))
+        Point::from(*self).min(other.into()).into()
     }
 
-    fn parse_segment(split: &mut std::str::Split<char>) -> Result<isize, ParsePoint3DError> {
-        Ok(split.next()
-            .ok_or(ParsePoint3DError::InvalidLength)?
-            .trim()
-            .parse::<isize>()?)
+    pub fn scale(&self, other: Self) -> Self {
+        Point::from(*self).scale(other.into()).into()
+    }
+
+    pub fn map(&self, f: impl Fn(isize) -> isize) -> Self {
+        Point::from(*self).map(f).into()
     }
 }
 
@@ -46,7 +47,7 @@ impl ops::Add<Point3D> for Point3D {
     type Output = Point3D;
 
     fn add(self, rhs: Point3D) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+        (Point::from(self) + Point::from(rhs)).into()
     }
 }
 
@@ -60,7 +61,7 @@ impl ops::Sub<Point3D> for Point3D {
     type Output = Point3D;
 
     fn sub(self, rhs: Point3D) -> Self::Output {
-        Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+        (Point::from(self) - Point::from(rhs)).into()
     }
 }
 
@@ -70,6 +71,16 @@ impl ops::SubAssign<Point3D> for Point3D {
     }
 }
 
+forward_scalar_ops!(Point3D);
+
+impl ops::Neg for Point3D {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        (-Point::from(self)).into()
+    }
+}
+
 impl From<(isize, isize, isize)> for Point3D {
     fn from(value: (isize, isize, isize)) -> Self {
         Self(value.0, value.1, value.2)
@@ -83,37 +94,24 @@ impl From<[isize; 3]> for Point3D {
 }
 
 impl TryFrom<&[isize]> for Point3D {
-    type Error = ParsePoint3DError;
-    
+    type Error = ParsePointError;
+
     fn try_from(value: &[isize]) -> Result<Self, Self::Error> {
         if value.len() == 3 {
             Ok(Self(value[0], value[1], value[2]))
         } else {
-            Err(ParsePoint3DError::InvalidLength)
+            Err(ParsePointError::InvalidLength { expected: 3, found: value.len() })
         }
     }
 }
 
+/// Former name of the error, from before [`Point3D`] was parsed through [`Point`].
+pub type ParsePoint3DError = ParsePointError;
+
 impl FromStr for Point3D {
-    type Err = ParsePoint3DError;
+    type Err = ParsePointError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(',');
-        let x = Self::parse_segment(&mut split)?;
-        let y = Self::parse_segment(&mut split)?;
-        let z = Self::parse_segment(&mut split)?;
-        if split.next().is_some() {
-            Err(ParsePoint3DError::InvalidLength)
-        } else {
-            Ok(Self(x, y, z))
-        }
+        Point::<3>::from_str(s).map(Self::from)
     }
 }
-
-#[derive(Debug, Clone, Error)]
-pub enum ParsePoint3DError {
-    #[error("should have 3 segments for Point3D")]
-    InvalidLength,
-    #[error("couldn't parse ints")]
-    ParseInt(#[from] ParseIntError),
-}
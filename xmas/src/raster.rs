@@ -0,0 +1,154 @@
+use crate::{map2d::Map2D, point2d::Point2D};
+
+/// Bresenham line from `from` to `to`, both included. Diagonal steps are allowed, so every point
+/// touches the previous one by at least a corner. Swapping the ends gives the same points reversed.
+pub fn line(from: Point2D, to: Point2D) -> Vec<Point2D> {
+    // Ties round differently depending on the direction, so always trace from the same end
+    if (to.0, to.1) < (from.0, from.1) {
+        let mut points = line(to, from);
+        points.reverse();
+        return points;
+    }
+
+    let delta = to - from;
+    let step = delta.map(isize::signum);
+    let (dx, dy) = (delta.0.abs(), -delta.1.abs());
+
+    let mut points = Vec::with_capacity(dx.max(-dy) as usize + 1);
+    let mut point = from;
+    let mut error = dx + dy;
+    loop {
+        points.push(point);
+        if point == to {
+            return points;
+        }
+
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            point.0 += step.0;
+        }
+        if doubled <= dx {
+            error += dx;
+            point.1 += step.1;
+        }
+    }
+}
+
+/// Every tile the segment between the centers of `from` and `to` passes through. When it goes
+/// exactly through a corner, both tiles beside the corner are included.
+pub fn supercover_line(from: Point2D, to: Point2D) -> Vec<Point2D> {
+    let delta = to - from;
+    let step = delta.map(isize::signum);
+    let (nx, ny) = (delta.0.abs(), delta.1.abs());
+
+    let mut points = vec![from];
+    let mut point = from;
+    let (mut ix, mut iy) = (0, 0);
+    while ix < nx || iy < ny {
+        // Compares (0.5 + ix) / nx with (0.5 + iy) / ny without fractions
+        match ((1 + 2 * ix) * ny).cmp(&((1 + 2 * iy) * nx)) {
+            std::cmp::Ordering::Equal => {
+                points.push(Point2D(point.0 + step.0, point.1));
+                points.push(Point2D(point.0, point.1 + step.1));
+                point += step;
+                ix += 1;
+                iy += 1;
+            },
+            std::cmp::Ordering::Less => {
+                point.0 += step.0;
+                ix += 1;
+            },
+            std::cmp::Ordering::Greater => {
+                point.1 += step.1;
+                iy += 1;
+            },
+        }
+        points.push(point);
+    }
+    points
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RayStop {
+    /// The tile that matched.
+    Hit(Point2D),
+    LeftMap,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RayCast {
+    /// Tiles passed through, not including the one that was hit.
+    pub path: Vec<Point2D>,
+    pub stop: RayStop,
+}
+
+impl<T> Map2D<T> {
+    /// Walks from `from` by `step` until a tile matches `is_hit` or the ray leaves the map. The
+    /// tile at `from` is checked too.
+    pub fn cast_ray(&self, from: Point2D, step: impl Into<Point2D>, is_hit: impl Fn(&T) -> bool) -> RayCast {
+        let step = step.into();
+        assert_ne!(step, Point2D::ZERO, "rays should move");
+
+        let mut path = Vec::new();
+        let mut point = from;
+        while let Some(tile) = self.get_tile(point) {
+            if is_hit(tile) {
+                return RayCast { path, stop: RayStop::Hit(point) };
+            }
+            path.push(point);
+            point += step;
+        }
+        RayCast { path, stop: RayStop::LeftMap }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{direction::Direction, map2d::ByteMap};
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[case(Point2D(0, 0), Point2D(4, 2), vec![Point2D(0, 0), Point2D(1, 1), Point2D(2, 1), Point2D(3, 2), Point2D(4, 2)])]
+    #[case(Point2D(2, 2), Point2D(2, -1), vec![Point2D(2, 2), Point2D(2, 1), Point2D(2, 0), Point2D(2, -1)])]
+    #[case(Point2D(0, 0), Point2D(-2, 2), vec![Point2D(0, 0), Point2D(-1, 1), Point2D(-2, 2)])]
+    #[case(Point2D(5, 5), Point2D(5, 5), vec![Point2D(5, 5)])]
+    fn draws_lines(#[case] from: Point2D, #[case] to: Point2D, #[case] expected: Vec<Point2D>) {
+        assert_eq!(line(from, to), expected);
+
+        let mut reversed = line(to, from);
+        reversed.reverse();
+        assert_eq!(reversed, expected);
+    }
+
+    #[rstest]
+    #[case(Point2D(0, 0), Point2D(2, 2), 7)]
+    #[case(Point2D(0, 0), Point2D(3, 1), 6)]
+    #[case(Point2D(0, 0), Point2D(4, 1), 6)]
+    #[case(Point2D(0, 0), Point2D(0, -3), 4)]
+    fn covers_every_touched_tile(#[case] from: Point2D, #[case] to: Point2D, #[case] expected: usize) {
+        let points = supercover_line(from, to);
+
+        assert_eq!(points.len(), expected);
+        assert_eq!(points.first(), Some(&from));
+        assert_eq!(points.last(), Some(&to));
+    }
+
+    #[test]
+    fn casts_rays() {
+        let map = ByteMap::from_str("..S..\n.....\n..^..\n.....\n").unwrap();
+
+        let down = map.cast_ray(Point2D(2, 0), Direction::Down, |&t| t == b'^');
+        assert_eq!(down.stop, RayStop::Hit(Point2D(2, 2)));
+        assert_eq!(down.path, vec![Point2D(2, 0), Point2D(2, 1)]);
+
+        let diagonal = map.cast_ray(Point2D(1, 0), Point2D(1, 1), |&t| t == b'^');
+        assert_eq!(diagonal.stop, RayStop::LeftMap);
+        assert_eq!(diagonal.path.len(), 4);
+
+        let outside = map.cast_ray(Point2D(-1, 0), Direction::Right, |_| true);
+        assert_eq!(outside, RayCast { path: vec![], stop: RayStop::LeftMap });
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+
+use crate::point2d::Point2D;
+
+/// Axis-aligned rectangle. Both corners are inclusive, so it always holds at least one point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect2D {
+    min: Point2D,
+    max: Point2D,
+}
+
+impl Rect2D {
+    /// Rectangle between any two opposite corners.
+    pub fn from_corners(a: Point2D, b: Point2D) -> Self {
+        Self { min: a.min(b), max: a.max(b) }
+    }
+
+    /// Rectangle of `size` starting at `origin`, if it isn't empty.
+    pub fn from_size(origin: Point2D, size: Point2D) -> Option<Self> {
+        (size.0 > 0 && size.1 > 0).then(|| Self { min: origin, max: origin + size - Point2D(1, 1) })
+    }
+
+    pub fn min(&self) -> Point2D {
+        self.min
+    }
+
+    pub fn max(&self) -> Point2D {
+        self.max
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.0.abs_diff(self.min.0) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.1.abs_diff(self.min.1) + 1
+    }
+
+    pub fn size(&self) -> Point2D {
+        Point2D(self.width() as isize, self.height() as isize)
+    }
+
+    pub fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+
+    pub fn contains(&self, point: Point2D) -> bool {
+        (self.min.0..=self.max.0).contains(&point.0) && (self.min.1..=self.max.1).contains(&point.1)
+    }
+
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        self.contains(other.min) && self.contains(other.max)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        (min.0 <= max.0 && min.1 <= max.1).then_some(Self { min, max })
+    }
+
+    /// Smallest rectangle holding both.
+    pub fn union(&self, other: &Self) -> Self {
+        Self { min: self.min.min(other.min), max: self.max.max(other.max) }
+    }
+
+    /// Row by row, top to bottom.
+    pub fn iter_points(&self) -> impl Iterator<Item = Point2D> + '_ {
+        (self.min.1..=self.max.1).flat_map(|y| (self.min.0..=self.max.0).map(move |x| Point2D(x, y)))
+    }
+
+    /// Points on the border, clockwise from the top left corner, each of them once.
+    pub fn iter_edge(&self) -> impl Iterator<Item = Point2D> + '_ {
+        let Self { min, max } = *self;
+        let top = (min.0..=max.0).map(move |x| Point2D(x, min.1));
+        let right = (min.1 + 1..=max.1).map(move |y| Point2D(max.0, y));
+        let bottom = (min.0..max.0).rev().map(move |x| Point2D(x, max.1)).filter(move |_| max.1 > min.1);
+        let left = (min.1 + 1..max.1).rev().map(move |y| Point2D(min.0, y)).filter(move |_| max.0 > min.0);
+        top.chain(right).chain(bottom).chain(left)
+    }
+
+    pub fn corners(&self) -> [Point2D; 4] {
+        [self.min, Point2D(self.max.0, self.min.1), self.max, Point2D(self.min.0, self.max.1)]
+    }
+
+    /// What's left after cutting `other` out, as up to 4 rectangles that don't overlap.
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![*self];
+        };
+
+        let mut pieces = Vec::new();
+        if cut.min.1 > self.min.1 {
+            pieces.push(Self { min: self.min, max: Point2D(self.max.0, cut.min.1 - 1) });
+        }
+        if cut.max.1 < self.max.1 {
+            pieces.push(Self { min: Point2D(self.min.0, cut.max.1 + 1), max: self.max });
+        }
+        if cut.min.0 > self.min.0 {
+            pieces.push(Self { min: Point2D(self.min.0, cut.min.1), max: Point2D(cut.min.0 - 1, cut.max.1) });
+        }
+        if cut.max.0 < self.max.0 {
+            pieces.push(Self { min: Point2D(cut.max.0 + 1, cut.min.1), max: Point2D(self.max.0, cut.max.1) });
+        }
+        pieces
+    }
+}
+
+impl Display for Rect2D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    #[rstest]
+    #[case(Point2D(2, 5), Point2D(11, 1), 50)]
+    #[case(Point2D(11, 1), Point2D(2, 5), 50)]
+    #[case(Point2D(7, 3), Point2D(7, 3), 1)]
+    #[case(Point2D(-2, 0), Point2D(2, 0), 5)]
+    fn area_is_inclusive(#[case] a: Point2D, #[case] b: Point2D, #[case] expected: usize) {
+        let rect = Rect2D::from_corners(a, b);
+
+        assert_eq!(rect.area(), expected);
+        assert_eq!(rect.iter_points().count(), expected);
+        assert!(rect.contains(a) && rect.contains(b));
+    }
+
+    #[rstest]
+    #[case(Point2D(0, 0), Point2D(3, 2), 10)]
+    #[case(Point2D(0, 0), Point2D(3, 0), 4)]
+    #[case(Point2D(0, 0), Point2D(0, 3), 4)]
+    #[case(Point2D(0, 0), Point2D(0, 0), 1)]
+    fn iterates_edge_once(#[case] a: Point2D, #[case] b: Point2D, #[case] expected: usize) {
+        let rect = Rect2D::from_corners(a, b);
+        let edge = rect.iter_edge().collect::<Vec<_>>();
+
+        assert_eq!(edge.len(), expected);
+        assert_eq!(edge.iter().collect::<HashSet<_>>().len(), expected);
+        assert!(edge.iter().all(|&p| p.0 == a.0 || p.0 == b.0 || p.1 == a.1 || p.1 == b.1));
+    }
+
+    #[test]
+    fn intersects_and_unites() {
+        let a = Rect2D::from_corners(Point2D(0, 0), Point2D(4, 4));
+        let b = Rect2D::from_corners(Point2D(3, 2), Point2D(8, 3));
+
+        assert_eq!(a.intersection(&b), Some(Rect2D::from_corners(Point2D(3, 2), Point2D(4, 3))));
+        assert_eq!(a.union(&b), Rect2D::from_corners(Point2D(0, 0), Point2D(8, 4)));
+        assert_eq!(a.intersection(&Rect2D::from_corners(Point2D(5, 5), Point2D(6, 6))), None);
+        assert!(a.union(&b).contains_rect(&b));
+    }
+
+    #[rstest]
+    #[case(Rect2D::from_corners(Point2D(1, 1), Point2D(2, 2)), 4)]
+    #[case(Rect2D::from_corners(Point2D(-5, 1), Point2D(2, 2)), 3)]
+    #[case(Rect2D::from_corners(Point2D(-5, -5), Point2D(10, 10)), 0)]
+    #[case(Rect2D::from_corners(Point2D(7, 7), Point2D(10, 10)), 1)]
+    fn subtracts_into_disjoint_pieces(#[case] cut: Rect2D, #[case] piece_count: usize) {
+        let rect = Rect2D::from_corners(Point2D(0, 0), Point2D(4, 4));
+        let pieces = rect.subtract(&cut);
+
+        assert_eq!(pieces.len(), piece_count);
+        let points = pieces.iter().flat_map(|p| p.iter_points().collect::<Vec<_>>()).collect::<Vec<_>>();
+        let expected = rect.iter_points().filter(|&p| !cut.contains(p)).collect::<HashSet<_>>();
+        assert_eq!(points.len(), expected.len());
+        assert_eq!(points.into_iter().collect::<HashSet<_>>(), expected);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::{direction3d::Direction3D, point::Point, point3d::Point3D};
+
+/// One of the 24 ways to turn something in 3D without mirroring it. Stored as the axis each
+/// component is taken from, and the sign it gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation3D {
+    axes: [usize; 3],
+    signs: [isize; 3],
+}
+
+impl Rotation3D {
+    pub const IDENTITY: Self = Self { axes: [0, 1, 2], signs: [1, 1, 1] };
+
+    /// Quarter turn around the x axis, taking y to z.
+    pub const AROUND_X: Self = Self { axes: [0, 2, 1], signs: [1, -1, 1] };
+    /// Quarter turn around the y axis, taking z to x.
+    pub const AROUND_Y: Self = Self { axes: [2, 1, 0], signs: [1, 1, -1] };
+    /// Quarter turn around the z axis, taking x to y.
+    pub const AROUND_Z: Self = Self { axes: [1, 0, 2], signs: [-1, 1, 1] };
+
+    /// Every orientation, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Self> {
+        const PERMUTATIONS: [([usize; 3], isize); 6] = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+
+        PERMUTATIONS.into_iter().flat_map(|(axes, parity)| {
+            (0..8)
+                .map(|bits| [0, 1, 2].map(|i| if bits & (1 << i) == 0 { 1 } else { -1 }))
+                .filter(move |signs| signs.iter().product::<isize>() == parity)
+                .map(move |signs| Self { axes, signs })
+        })
+    }
+
+    pub fn apply(&self, point: Point3D) -> Point3D {
+        let p = Point::from(point).components();
+        Point([0, 1, 2].map(|i| self.signs[i] * p[self.axes[i]])).into()
+    }
+
+    pub fn apply_direction(&self, direction: Direction3D) -> Direction3D {
+        Direction3D::try_from(self.apply(direction.as_point())).expect("rotations should keep unit vectors")
+    }
+
+    /// Rotation doing `first`, then `self`.
+    pub fn compose(&self, first: &Self) -> Self {
+        Self {
+            axes: [0, 1, 2].map(|i| first.axes[self.axes[i]]),
+            signs: [0, 1, 2].map(|i| self.signs[i] * first.signs[self.axes[i]]),
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut inverse = Self::IDENTITY;
+        for i in 0..3 {
+            inverse.axes[self.axes[i]] = i;
+            inverse.signs[self.axes[i]] = self.signs[i];
+        }
+        inverse
+    }
+}
+
+/// Finds how to turn and move `points` so that at least `min_overlap` of them land on `reference`,
+/// like scanners that saw the same beacons from different places. Returns the rotation and then
+/// the translation to apply.
+pub fn find_alignment(reference: &[Point3D], points: &[Point3D], min_overlap: usize) -> Option<(Rotation3D, Point3D)> {
+    Rotation3D::all().find_map(|rotation| {
+        let rotated = points.iter().map(|&p| rotation.apply(p)).collect::<Vec<_>>();
+        let mut votes = HashMap::new();
+        for &r in reference {
+            for &p in &rotated {
+                let offset = r - p;
+                let count = votes.entry(offset).or_insert(0);
+                *count += 1;
+                if *count >= min_overlap {
+                    return Some((rotation, offset));
+                }
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction3d::DIRECTIONS_3D;
+    use rstest::rstest;
+    use std::collections::HashSet;
+
+    #[test]
+    fn has_24_distinct_orientations() {
+        let all = Rotation3D::all().collect::<Vec<_>>();
+        let images = all.iter().map(|r| r.apply(Point3D(1, 2, 3))).collect::<HashSet<_>>();
+
+        assert_eq!(all.len(), 24);
+        assert_eq!(images.len(), 24);
+        assert_eq!(all[0], Rotation3D::IDENTITY);
+    }
+
+    #[rstest]
+    #[case(Rotation3D::AROUND_X, Point3D(0, 1, 0), Point3D(0, 0, 1))]
+    #[case(Rotation3D::AROUND_Y, Point3D(0, 0, 1), Point3D(1, 0, 0))]
+    #[case(Rotation3D::AROUND_Z, Point3D(1, 0, 0), Point3D(0, 1, 0))]
+    fn turns_around_axes(#[case] rotation: Rotation3D, #[case] point: Point3D, #[case] expected: Point3D) {
+        assert_eq!(rotation.apply(point), expected);
+        assert!(Rotation3D::all().any(|r| r == rotation));
+    }
+
+    #[test]
+    fn composes_and_inverts() {
+        let p = Point3D(1, -2, 5);
+        for a in Rotation3D::all() {
+            assert_eq!(a.inverse().apply(a.apply(p)), p);
+            assert_eq!(a.compose(&a.inverse()), Rotation3D::IDENTITY);
+
+            for b in Rotation3D::all() {
+                assert_eq!(a.compose(&b).apply(p), a.apply(b.apply(p)));
+            }
+        }
+
+        let quarter = Rotation3D::AROUND_Z;
+        let full_turn = quarter.compose(&quarter).compose(&quarter).compose(&quarter);
+        assert_eq!(full_turn, Rotation3D::IDENTITY);
+    }
+
+    #[test]
+    fn turns_directions() {
+        for rotation in Rotation3D::all() {
+            let turned = DIRECTIONS_3D.map(|d| rotation.apply_direction(d));
+            assert_eq!(turned.iter().collect::<HashSet<_>>().len(), 6);
+            assert_eq!(turned[0].inverse(), rotation.apply_direction(DIRECTIONS_3D[0].inverse()));
+        }
+    }
+
+    #[test]
+    fn aligns_point_clouds() {
+        let reference = vec![Point3D(0, 0, 0), Point3D(4, 1, 0), Point3D(3, 3, 1), Point3D(-2, 5, 7), Point3D(9, 9, 9)];
+        let secret = Rotation3D::all().nth(17).unwrap();
+        let offset = Point3D(10, -20, 30);
+
+        // Scanner sees the first four plus one unrelated point
+        let seen = reference[..4]
+            .iter()
+            .map(|&p| secret.inverse().apply(p - offset))
+            .chain([Point3D(100, 100, 100)])
+            .collect::<Vec<_>>();
+
+        let (rotation, translation) = find_alignment(&reference, &seen, 4).unwrap();
+        assert_eq!(rotation, secret);
+        assert_eq!(translation, offset);
+        assert_eq!(find_alignment(&reference, &seen, 5), None);
+    }
+}
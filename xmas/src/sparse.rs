@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::{map2d::Map2D, point2d::Point2D, rect2d::Rect2D};
+
+/// Grid that only stores the tiles that were set, for huge or unbounded coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    tiles: HashMap<Point2D, T>,
+    bounds: Option<Rect2D>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self { tiles: HashMap::new(), bounds: None }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_tile(&self, point: Point2D) -> Option<&T> {
+        self.tiles.get(&point)
+    }
+
+    pub fn get_tile_mut(&mut self, point: Point2D) -> Option<&mut T> {
+        self.tiles.get_mut(&point)
+    }
+
+    /// Returns the tile that was there before, if any.
+    pub fn set_tile(&mut self, point: Point2D, tile: T) -> Option<T> {
+        let tile_rect = Rect2D::from_corners(point, point);
+        self.bounds = Some(self.bounds.map_or(tile_rect, |b| b.union(&tile_rect)));
+        self.tiles.insert(point, tile)
+    }
+
+    pub fn remove_tile(&mut self, point: Point2D) -> Option<T> {
+        let removed = self.tiles.remove(&point)?;
+        let on_edge = |b: Rect2D| point.0 == b.min().0 || point.1 == b.min().1 || point.0 == b.max().0 || point.1 == b.max().1;
+        if self.bounds.is_some_and(on_edge) {
+            self.bounds = Self::compute_bounds(self.tiles.keys().copied());
+        }
+        Some(removed)
+    }
+
+    pub fn contains(&self, point: Point2D) -> bool {
+        self.tiles.contains_key(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Smallest rectangle holding every set tile.
+    pub fn bounds(&self) -> Option<Rect2D> {
+        self.bounds
+    }
+
+    /// Set tiles in no particular order.
+    pub fn iter_with_points(&self) -> impl Iterator<Item = (Point2D, &T)> + '_ {
+        self.tiles.iter().map(|(&p, t)| (p, t))
+    }
+
+    pub fn iter_points(&self) -> impl Iterator<Item = Point2D> + '_ {
+        self.tiles.keys().copied()
+    }
+
+    /// Tiles of a dense map, placed with its top left corner at `origin`. Tiles that fail `keep`
+    /// are left unset, like the empty space around a shape.
+    pub fn from_map(map: &Map2D<T>, origin: Point2D, keep: impl Fn(&T) -> bool) -> Self
+        where T: Clone
+    {
+        map.iter_with_points()
+            .filter(|(_, t)| keep(t))
+            .map(|(p, t)| (p + origin, t.clone()))
+            .collect()
+    }
+
+    fn compute_bounds(points: impl Iterator<Item = Point2D>) -> Option<Rect2D> {
+        points.map(|p| Rect2D::from_corners(p, p)).reduce(|a, b| a.union(&b))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Dense copy of the tiles inside `rect`, with `empty` wherever nothing was set.
+    pub fn to_map(&self, rect: Rect2D, empty: T) -> Map2D<T> {
+        Map2D::from_fn(rect.size(), |p| self.get_tile(rect.min() + p).cloned().unwrap_or_else(|| empty.clone()))
+    }
+
+    /// Dense copy of everything inside the bounds, along with where its top left corner was.
+    /// Empty when no tile is set.
+    pub fn to_map_in_bounds(&self, empty: T) -> (Map2D<T>, Point2D) {
+        match self.bounds {
+            Some(bounds) => (self.to_map(bounds, empty), bounds.min()),
+            None => (Map2D::from_fn(Point2D::ZERO, |_| empty.clone()), Point2D::ZERO),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point2D, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2D, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (point, tile) in iter {
+            grid.set_tile(point, tile);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map2d::ByteMap;
+    use std::str::FromStr;
+
+    #[test]
+    fn tracks_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.set_tile(Point2D(100_000, -5), 'a');
+        grid.set_tile(Point2D(-3, 7), 'b');
+        grid.set_tile(Point2D(2, 2), 'c');
+        assert_eq!(grid.bounds(), Some(Rect2D::from_corners(Point2D(-3, -5), Point2D(100_000, 7))));
+
+        grid.remove_tile(Point2D(100_000, -5));
+        assert_eq!(grid.bounds(), Some(Rect2D::from_corners(Point2D(-3, 2), Point2D(2, 7))));
+
+        grid.remove_tile(Point2D(-3, 7));
+        grid.remove_tile(Point2D(2, 2));
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn set_tile_returns_previous() {
+        let mut grid = SparseGrid::new();
+
+        assert_eq!(grid.set_tile(Point2D(1, 1), 1), None);
+        assert_eq!(grid.set_tile(Point2D(1, 1), 2), Some(1));
+        assert_eq!(grid.get_tile(Point2D(1, 1)), Some(&2));
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn converts_to_and_from_map() {
+        let map = ByteMap::from_str("#..\n.#.\n..#\n").unwrap();
+        let grid = SparseGrid::from_map(&map, Point2D(10, 20), |&t| t == b'#');
+
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains(Point2D(11, 21)));
+
+        let (dense, origin) = grid.to_map_in_bounds(b'.');
+        assert_eq!(origin, Point2D(10, 20));
+        assert_eq!(dense, map);
+
+        let viewport = grid.to_map(Rect2D::from_corners(Point2D(11, 20), Point2D(13, 21)), b' ');
+        assert_eq!(viewport.to_string(), "   \n#  \n");
+
+        let (empty, origin) = SparseGrid::<u8>::new().to_map_in_bounds(b'.');
+        assert_eq!((empty.size(), origin), (Point2D::ZERO, Point2D::ZERO));
+    }
+}